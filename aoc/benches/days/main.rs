@@ -0,0 +1,140 @@
+//! Parsing and both parts of every day, on the committed example inputs and on
+//! larger generated ones.
+//!
+//! The generated inputs come from `aoc_gen` with the day number as a fixed
+//! seed, so results can be compared
+//! across runs: record a baseline with `cargo bench -p aoc -- --save-baseline main`
+//! and compare a later run against it with `cargo bench -p aoc -- --baseline main`.
+//! Pass a filter such as `day11/` to only run one day.
+
+use std::{fs, hint::black_box, path::Path};
+
+use aoc_common::{Params, Solution};
+use aoc_gen::Rng;
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use day01::Day01;
+use day02::Day02;
+use day03::Day03;
+use day04::Day04;
+use day05::Day05;
+use day06::Day06;
+use day07::Day07;
+use day08::Day08;
+use day09::Day09;
+use day10::Day10;
+use day11::Day11;
+use day12::Day12;
+use day13::Day13;
+use day14::Day14;
+use day15::Day15;
+use day16::Day16;
+use day18::Day18;
+use day19::Day19;
+
+/// Reads an input committed under `dayNN/input/`.
+fn example(day: &str, file: &str) -> String {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join(day)
+        .join("input")
+        .join(file);
+    fs::read_to_string(&path).unwrap_or_else(|err| panic!("{}: {err}", path.display()))
+}
+
+fn bench<S: Solution>(c: &mut Criterion, name: &str, input: &str) {
+    bench_with::<S>(c, name, input, &[]);
+}
+
+/// Benchmarks `S` on `input` as group `name`, with puzzle parameters given as
+/// `name=value` assignments.
+fn bench_with<S: Solution>(c: &mut Criterion, name: &str, input: &str, assignments: &[&str]) {
+    let mut params = Params::new(S::PARAMS);
+    for assignment in assignments {
+        params
+            .assign(assignment)
+            .unwrap_or_else(|err| panic!("{name}: {err}"));
+    }
+
+    let mut parsed = S::parse(input).unwrap_or_else(|err| panic!("{name}: {err}"));
+    S::configure(&mut parsed, &params);
+
+    let mut group = c.benchmark_group(name);
+    if input.len() > 5_000 {
+        group.sample_size(10);
+    }
+
+    group.bench_function("parse", |b| b.iter(|| S::parse(black_box(input))));
+    group.bench_function("part1", |b| b.iter(|| S::part1(black_box(&parsed))));
+    group.bench_function("part2", |b| b.iter(|| S::part2(black_box(&parsed))));
+    group.finish();
+}
+
+fn examples(c: &mut Criterion) {
+    bench::<Day01>(c, "day01/example", &example("day01", "test_input.txt"));
+    bench::<Day02>(c, "day02/example", &example("day02", "test_input.txt"));
+    bench::<Day03>(c, "day03/example", &example("day03", "test_input_2.txt"));
+    bench::<Day04>(c, "day04/example", &example("day04", "test_input.txt"));
+    bench::<Day05>(c, "day05/example", &example("day05", "test_input.txt"));
+    bench::<Day06>(c, "day06/example", &example("day06", "test_input.txt"));
+    bench::<Day07>(c, "day07/example", &example("day07", "test_input.txt"));
+    bench::<Day08>(c, "day08/example", &example("day08", "test_input.txt"));
+    bench::<Day09>(c, "day09/example", &example("day09", "test_input.txt"));
+    bench::<Day10>(c, "day10/example", &example("day10", "test_input.txt"));
+    bench::<Day11>(c, "day11/example", &example("day11", "test_input.txt"));
+    bench::<Day12>(c, "day12/example", &example("day12", "test_input.txt"));
+    bench::<Day13>(c, "day13/example", &example("day13", "test_input.txt"));
+    bench_with::<Day14>(
+        c,
+        "day14/example",
+        &example("day14", "test_input.txt"),
+        &["width=11", "height=7"],
+    );
+    bench::<Day15>(c, "day15/example", &example("day15", "test_input.txt"));
+    bench::<Day16>(c, "day16/example", &example("day16", "test_input_2.txt"));
+    bench_with::<Day18>(
+        c,
+        "day18/example",
+        &example("day18", "test_input.txt"),
+        &["max-coord=6", "bytes=12"],
+    );
+    bench::<Day19>(c, "day19/example", &example("day19", "test_input.txt"));
+}
+
+/// Benchmarks every `dayNN: DayNN` on a puzzle sized input from
+/// `aoc_gen::dayNN`, seeded with the day number.
+macro_rules! generated {
+    ($c:expr, $($day:ident: $solution:ty = $seed:literal,)*) => {
+        $(
+            let input = aoc_gen::$day::input(&mut Rng::new($seed), aoc_gen::$day::PUZZLE_SIZE);
+            bench::<$solution>($c, concat!(stringify!($day), "/generated"), &input);
+        )*
+    };
+}
+
+fn generated(c: &mut Criterion) {
+    generated! {
+        c,
+        day01: Day01 = 1,
+        day02: Day02 = 2,
+        day03: Day03 = 3,
+        day04: Day04 = 4,
+        day05: Day05 = 5,
+        day06: Day06 = 6,
+        day07: Day07 = 7,
+        day08: Day08 = 8,
+        day09: Day09 = 9,
+        day10: Day10 = 10,
+        day11: Day11 = 11,
+        day12: Day12 = 12,
+        day13: Day13 = 13,
+        day14: Day14 = 14,
+        day15: Day15 = 15,
+        day16: Day16 = 16,
+        day18: Day18 = 18,
+        day19: Day19 = 19,
+    }
+}
+
+criterion_group!(benches, examples, generated);
+criterion_main!(benches);
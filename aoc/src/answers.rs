@@ -0,0 +1,249 @@
+//! Answers accepted with `aoc accept`, by day and input. Every `aoc run` of
+//! the same input has to reproduce them, so that a refactor cannot change a
+//! known good answer unnoticed.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use aoc_common::{input_hash, Params, Part, Report};
+use toml::{Table, Value};
+
+const HEADER: &str = "# Answers accepted with `aoc accept`, checked by every `aoc run`.\n\n";
+
+/// Accepted answers to part 1 and part 2.
+type Accepted = [Option<String>; 2];
+
+pub struct AnswerStore {
+    path: PathBuf,
+    /// By day, then by [`input_key`].
+    days: BTreeMap<u8, BTreeMap<String, Accepted>>,
+}
+
+/// What the answers depend on: the hash of the input, followed by the
+/// parameters if the day has any.
+pub fn input_key(input: &str, params: &Params) -> String {
+    let hash = input_hash(input);
+    match params.to_string() {
+        params if params.is_empty() => hash,
+        params => format!("{hash} {params}"),
+    }
+}
+
+impl AnswerStore {
+    /// Reads the store at `path`; a missing file is an empty store.
+    pub fn load(path: &Path) -> Result<AnswerStore, String> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(format!("cannot read {}: {err}", path.display())),
+        };
+        let table: Table = content
+            .parse()
+            .map_err(|err| format!("{}: {err}", path.display()))?;
+
+        let mut days = BTreeMap::new();
+        for (day_key, inputs) in &table {
+            let malformed = || format!("{}: malformed answers for `{day_key}`", path.display());
+            let day = day_key
+                .strip_prefix("day")
+                .and_then(|day| day.parse().ok())
+                .ok_or_else(malformed)?;
+            let inputs = inputs
+                .as_table()
+                .ok_or_else(malformed)?
+                .iter()
+                .map(|(key, parts)| Some((key.clone(), parse_parts(parts)?)))
+                .collect::<Option<_>>()
+                .ok_or_else(malformed)?;
+            days.insert(day, inputs);
+        }
+
+        Ok(AnswerStore {
+            path: path.to_path_buf(),
+            days,
+        })
+    }
+
+    pub fn get(&self, day: u8, key: &str, part: Part) -> Option<&str> {
+        let accepted = self.days.get(&day)?.get(key)?;
+        accepted[part_index(part)].as_deref()
+    }
+
+    /// Accepts `answer`, replacing the one accepted before.
+    pub fn accept(&mut self, day: u8, key: &str, part: Part, answer: &str) {
+        let accepted = self
+            .days
+            .entry(day)
+            .or_default()
+            .entry(key.to_string())
+            .or_default();
+        accepted[part_index(part)] = Some(answer.to_string());
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let table: Table = self
+            .days
+            .iter()
+            .map(|(day, inputs)| {
+                let inputs = inputs
+                    .iter()
+                    .map(|(key, accepted)| (key.clone(), Value::Table(format_parts(accepted))))
+                    .collect();
+                (format!("day{day:02}"), Value::Table(inputs))
+            })
+            .collect();
+        let content = format!("{HEADER}{table}");
+
+        let write = || -> std::io::Result<()> {
+            if let Some(dir) = self.path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            fs::write(&self.path, content)
+        };
+        write().map_err(|err| format!("cannot write {}: {err}", self.path.display()))
+    }
+
+    /// Fails with one line per part of `report` that differs from the answer
+    /// accepted for `key`.
+    pub fn check(&self, day: u8, key: &str, report: &Report) -> Result<(), String> {
+        let regressions: Vec<String> = report
+            .parts
+            .iter()
+            .filter_map(|part_report| {
+                let part = part_report.part;
+                let accepted = self.get(day, key, part)?;
+                let found = match &part_report.answer {
+                    Ok(answer) if answer == accepted => return None,
+                    Ok(answer) => answer.clone(),
+                    Err(err) => err.to_string(),
+                };
+                Some(format!(
+                    "day {day} part {part}: got {found}, but {accepted} was accepted for this input"
+                ))
+            })
+            .collect();
+
+        if regressions.is_empty() {
+            Ok(())
+        } else {
+            Err(regressions.join("\n"))
+        }
+    }
+}
+
+fn part_index(part: Part) -> usize {
+    usize::from(part.number() - 1)
+}
+
+/// `part1 = "..."` and `part2 = "..."`, either of them optional.
+fn parse_parts(parts: &Value) -> Option<Accepted> {
+    let mut accepted = Accepted::default();
+    for (part_key, answer) in parts.as_table()? {
+        let part: Part = part_key.strip_prefix("part")?.parse().ok()?;
+        accepted[part_index(part)] = Some(answer.as_str()?.to_string());
+    }
+    Some(accepted)
+}
+
+fn format_parts(accepted: &Accepted) -> Table {
+    [Part::One, Part::Two]
+        .into_iter()
+        .filter_map(|part| {
+            let answer = accepted[part_index(part)].clone()?;
+            Some((format!("part{part}"), Value::String(answer)))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use aoc_common::{PartReport, SolveError};
+
+    use super::*;
+
+    fn report(answers: [Result<&str, SolveError>; 2]) -> Report {
+        Report {
+            parse_elapsed: Duration::ZERO,
+            parts: [Part::One, Part::Two]
+                .into_iter()
+                .zip(answers)
+                .map(|(part, answer)| PartReport {
+                    part,
+                    answer: answer.map(str::to_string),
+                    elapsed: Duration::ZERO,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn check_test() {
+        let mut store = AnswerStore {
+            path: PathBuf::new(),
+            days: BTreeMap::new(),
+        };
+        store.accept(12, "abc", Part::One, "1930");
+        store.accept(12, "abc", Part::Two, "1206");
+
+        assert!(store
+            .check(12, "abc", &report([Ok("1930"), Ok("1206")]))
+            .is_ok());
+        assert!(store.check(12, "def", &report([Ok("1"), Ok("2")])).is_ok());
+        assert!(store.check(11, "abc", &report([Ok("1"), Ok("2")])).is_ok());
+        assert_eq!(
+            store.check(12, "abc", &report([Ok("1930"), Ok("1207")])),
+            Err("day 12 part 2: got 1207, but 1206 was accepted for this input".to_string())
+        );
+        let err = store
+            .check(12, "abc", &report([Err(SolveError::NoSolution), Ok("1")]))
+            .unwrap_err();
+        assert_eq!(err.lines().count(), 2);
+    }
+
+    #[test]
+    fn save_load_test() {
+        let dir = std::env::temp_dir().join(format!("aoc-answers-{}", std::process::id()));
+        let path = dir.join("answers.toml");
+
+        let mut store = AnswerStore::load(&path).unwrap();
+        store.accept(14, "abc width=11 height=7", Part::One, "12");
+        store.accept(3, "def", Part::Two, "48");
+        store.save().unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.contains("[day14.\"abc width=11 height=7\"]\npart1 = \"12\"\n"));
+        let store = AnswerStore::load(&path).unwrap();
+        assert_eq!(
+            store.get(14, "abc width=11 height=7", Part::One),
+            Some("12")
+        );
+        assert_eq!(store.get(14, "abc width=11 height=7", Part::Two), None);
+        assert_eq!(store.get(3, "def", Part::Two), Some("48"));
+
+        fs::write(&path, "[day03.def]\npart3 = \"48\"\n").unwrap();
+        assert!(AnswerStore::load(&path).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn input_key_test() {
+        let params = [aoc_common::Param {
+            name: "bytes",
+            help: "",
+            default: 1024,
+            max: u64::MAX,
+        }];
+        let hash = input_hash("1,2\n");
+        assert_eq!(input_key("1,2\n", &Params::new(&[])), hash);
+        assert_eq!(
+            input_key("1,2\n", &Params::new(&params)),
+            format!("{hash} bytes=1024")
+        );
+    }
+}
@@ -0,0 +1,159 @@
+use std::{io, path::Path};
+
+use aoc_anim::{debug, save_picture, Animate, Picture, Player, StepThrough};
+#[cfg(feature = "serde")]
+use aoc_common::{dump_parsed, solve_parsed};
+use aoc_common::{solve, Param, Params, ParseError, Part, Report, Solution};
+#[cfg(feature = "serde")]
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Parses a day's raw puzzle input and solves the requested parts.
+pub type Solver = fn(&str, &Params, &[Part]) -> Result<Report, ParseError>;
+
+/// Parses a day's raw puzzle input and writes it out as JSON.
+#[cfg(feature = "serde")]
+pub type Dumper = fn(&str, &Params) -> Result<String, ParseError>;
+
+/// Solves the requested parts of an input written by a [`Dumper`].
+#[cfg(feature = "serde")]
+pub type Loader = fn(&str, &[Part]) -> Result<Report, ParseError>;
+
+pub struct Day {
+    pub number: u8,
+    pub solve: Solver,
+    pub params: &'static [Param],
+    #[cfg(feature = "serde")]
+    pub dump_parsed: Dumper,
+    #[cfg(feature = "serde")]
+    pub solve_parsed: Loader,
+}
+
+impl Day {
+    #[cfg(not(feature = "serde"))]
+    const fn new<S: Solution>(number: u8) -> Day {
+        Day {
+            number,
+            solve: solve::<S>,
+            params: S::PARAMS,
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    const fn new<S: Solution>(number: u8) -> Day
+    where
+        S::Input: Serialize + DeserializeOwned,
+    {
+        Day {
+            number,
+            solve: solve::<S>,
+            params: S::PARAMS,
+            dump_parsed: dump_parsed::<S>,
+            solve_parsed: solve_parsed::<S>,
+        }
+    }
+}
+
+pub const DAYS: &[Day] = &[
+    Day::new::<day01::Day01>(1),
+    Day::new::<day02::Day02>(2),
+    Day::new::<day03::Day03>(3),
+    Day::new::<day04::Day04>(4),
+    Day::new::<day05::Day05>(5),
+    Day::new::<day06::Day06>(6),
+    Day::new::<day07::Day07>(7),
+    Day::new::<day08::Day08>(8),
+    Day::new::<day09::Day09>(9),
+    Day::new::<day10::Day10>(10),
+    Day::new::<day11::Day11>(11),
+    Day::new::<day12::Day12>(12),
+    Day::new::<day13::Day13>(13),
+    Day::new::<day14::Day14>(14),
+    Day::new::<day15::Day15>(15),
+    Day::new::<day16::Day16>(16),
+    Day::new::<day18::Day18>(18),
+    Day::new::<day19::Day19>(19),
+];
+
+pub fn find_day(day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|entry| entry.number == day)
+}
+
+/// The entry for `day` in one of the registries below.
+pub fn find_entry<F: Copy>(registry: &[(u8, F)], day: u8) -> Option<F> {
+    registry
+        .iter()
+        .find(|(number, _)| *number == day)
+        .map(|(_, entry)| *entry)
+}
+
+/// Parses a day's raw puzzle input and applies its parameters.
+fn parse_configured<S: Solution>(input: &str, params: &Params) -> Result<S::Input, ParseError> {
+    let mut parsed = S::parse(input)?;
+    S::configure(&mut parsed, params);
+    Ok(parsed)
+}
+
+/// Parses a day's raw puzzle input and plays its simulation.
+pub type Animator = fn(&str, &Params, &mut Player) -> Result<io::Result<()>, ParseError>;
+
+fn animate<S: Animate>(
+    input: &str,
+    params: &Params,
+    player: &mut Player,
+) -> Result<io::Result<()>, ParseError> {
+    let parsed = parse_configured::<S>(input, params)?;
+    Ok(player.play(S::frames(&parsed)))
+}
+
+/// Days whose simulation can be watched.
+pub const ANIMATIONS: &[(u8, Animator)] = &[
+    (6, animate::<day06::Day06>),
+    (14, animate::<day14::Day14>),
+    (15, animate::<day15::Day15>),
+    (16, animate::<day16::Day16>),
+];
+
+/// Parses a day's raw puzzle input and opens the debugger on its simulation,
+/// at the given step.
+pub type Debugger = fn(&str, &Params, usize) -> Result<io::Result<()>, ParseError>;
+
+fn step_through<S: StepThrough>(
+    input: &str,
+    params: &Params,
+    step: usize,
+) -> Result<io::Result<()>, ParseError> {
+    let parsed = parse_configured::<S>(input, params)?;
+    Ok(debug::<S>(&parsed, step))
+}
+
+/// Days whose simulation can be stepped through.
+pub const DEBUGGERS: &[(u8, Debugger)] = &[
+    (6, step_through::<day06::Day06>),
+    (15, step_through::<day15::Day15>),
+    (18, step_through::<day18::Day18>),
+];
+
+/// Parses a day's raw puzzle input and draws its picture to a file, with the
+/// given number of pixels per cell.
+pub type Painter = fn(&str, &Params, &Path, usize) -> Result<io::Result<()>, ParseError>;
+
+fn paint<S: Picture>(
+    input: &str,
+    params: &Params,
+    path: &Path,
+    scale: usize,
+) -> Result<io::Result<()>, ParseError> {
+    let parsed = parse_configured::<S>(input, params)?;
+    Ok(save_picture::<S>(&parsed, path, scale))
+}
+
+/// Days that can be drawn with `--render`.
+pub const PICTURES: &[(u8, Painter)] = &[
+    (6, paint::<day06::Day06>),
+    (10, paint::<day10::Day10>),
+    (12, paint::<day12::Day12>),
+    (14, paint::<day14::Day14>),
+    (15, paint::<day15::Day15>),
+    (16, paint::<day16::Day16>),
+    (18, paint::<day18::Day18>),
+];
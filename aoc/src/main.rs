@@ -0,0 +1,697 @@
+mod answers;
+mod days;
+mod scaffold;
+mod site;
+mod submissions;
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    str::FromStr,
+};
+
+use aoc_anim::Player;
+use aoc_common::{
+    format_duration, init_logging, json_report, set_jobs, Format, InputSource, Params, ParseError,
+    Part, INPUT_DIR_VAR,
+};
+use clap::{Args, Parser, Subcommand};
+use tracing::info_span;
+
+use answers::{input_key, AnswerStore};
+use site::{Client, Verdict};
+use submissions::{Submission, SubmissionLog};
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Advent of Code 2024 runner")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+
+    /// Show what the solvers are doing on stderr, more with -vv and -vvv
+    #[arg(short, long, action = clap::ArgAction::Count, global = true)]
+    verbose: u8,
+
+    /// Threads for the solvers that split their work [default: one per CPU]
+    #[arg(
+        short,
+        long,
+        value_name = "N",
+        global = true,
+        value_parser = clap::value_parser!(u16).range(1..)
+    )]
+    jobs: Option<u16>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve a single day, or every day with `all`
+    Run {
+        /// Day number or `all`
+        day: DaySelection,
+
+        /// Only solve the given part
+        #[arg(short, long)]
+        part: Option<Part>,
+
+        /// Puzzle input, `-` for stdin [default: `dayNN.txt` in $AOC_INPUT_DIR,
+        /// or `dayNN/input/input.txt`]
+        #[arg(short, long, value_name = "FILE")]
+        input: Option<InputSource>,
+
+        /// Sets a puzzle parameter like a grid size, e.g. `--param width=11`
+        #[arg(long = "param", value_name = "NAME=VALUE")]
+        params: Vec<String>,
+
+        /// `text`, or `json` for a machine-readable line per day
+        #[arg(long, default_value_t = Format::Text)]
+        format: Format,
+
+        /// Print the parsed input instead of solving it, for `--parsed` or to
+        /// look at
+        #[cfg(feature = "serde")]
+        #[arg(long, value_name = "FORMAT", conflicts_with = "render")]
+        dump_parsed: Option<DumpFormat>,
+
+        /// Solve an input written by `--dump-parsed`, with the parameters it
+        /// was written with, instead of parsing the puzzle input. The answers
+        /// are not checked, as the puzzle input they were accepted for is gone
+        #[cfg(feature = "serde")]
+        #[arg(
+            long,
+            value_name = "FILE",
+            conflicts_with_all = ["input", "params", "dump_parsed", "render"]
+        )]
+        parsed: Option<InputSource>,
+
+        /// Also draw the input and what was found in it, to a `.png` or `.ppm`
+        /// (days 6, 10, 12, 14, 15, 16 and 18)
+        #[arg(long, value_name = "FILE")]
+        render: Option<PathBuf>,
+
+        /// Pixels per grid cell for --render
+        #[arg(
+            long,
+            value_name = "N",
+            default_value_t = 4,
+            requires = "render",
+            value_parser = clap::value_parser!(u16).range(1..)
+        )]
+        scale: u16,
+
+        #[command(flatten)]
+        answers: AnswersArgs,
+    },
+    /// Lock in the answer to a part for the day's input, for `run` to check from then on
+    Accept {
+        /// Day number
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// Part to accept the answer to
+        part: Part,
+
+        /// Puzzle input, `-` for stdin [default: as for `run`]
+        #[arg(short, long, value_name = "FILE")]
+        input: Option<InputSource>,
+
+        /// Sets a puzzle parameter like a grid size, e.g. `--param width=11`
+        #[arg(long = "param", value_name = "NAME=VALUE")]
+        params: Vec<String>,
+
+        /// Replace a different answer that was accepted before
+        #[arg(long)]
+        force: bool,
+
+        #[command(flatten)]
+        answers: AnswersArgs,
+    },
+    /// Watch a day's simulation step by step in the terminal
+    Animate {
+        /// Day number, one of 6, 14, 15 and 16
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// Puzzle input, `-` for stdin [default: as for `run`]
+        #[arg(short, long, value_name = "FILE")]
+        input: Option<InputSource>,
+
+        /// Sets a puzzle parameter like a grid size, e.g. `--param width=11`
+        #[arg(long = "param", value_name = "NAME=VALUE")]
+        params: Vec<String>,
+
+        /// Frames per second; `+` and `-` change it while playing
+        #[arg(long, default_value_t = 10.0, value_parser = parse_fps)]
+        fps: f64,
+
+        /// Start paused on the first frame
+        #[arg(long)]
+        paused: bool,
+    },
+    /// Step through a day's simulation forwards and backwards in the terminal
+    Debug {
+        /// Day number, one of 6, 15 and 18
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// Puzzle input, `-` for stdin [default: as for `run`]
+        #[arg(short, long, value_name = "FILE")]
+        input: Option<InputSource>,
+
+        /// Sets a puzzle parameter like a grid size, e.g. `--param width=11`
+        #[arg(long = "param", value_name = "NAME=VALUE")]
+        params: Vec<String>,
+
+        /// Step to start at; the only one printed when stdout is not a terminal
+        #[arg(long, value_name = "N", default_value_t = 0)]
+        step: usize,
+    },
+    /// Create `dayNN/` from the `dayXX` template and register it everywhere
+    New {
+        /// Day number
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+    },
+    /// Download a day's puzzle input to where `run` looks for it
+    Fetch {
+        /// Day number
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// Download again even if the input is already there
+        #[arg(long)]
+        force: bool,
+
+        #[command(flatten)]
+        site: SiteArgs,
+    },
+    /// Submit an answer, unless it is known to be wrong already
+    Submit {
+        /// Day number
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// Part the answer is for
+        part: Part,
+
+        /// The answer, as the solver printed it
+        answer: String,
+
+        #[command(flatten)]
+        site: SiteArgs,
+    },
+}
+
+#[derive(Args)]
+struct SiteArgs {
+    /// Address of the puzzle site
+    #[arg(long, env = "AOC_BASE_URL", default_value = site::DEFAULT_BASE_URL)]
+    base_url: String,
+
+    /// File holding the site's `session` cookie [default: ~/.config/aoc/session]
+    #[arg(long, env = "AOC_SESSION_FILE", value_name = "FILE")]
+    session_file: Option<PathBuf>,
+
+    /// Where to keep the record of submitted answers [default: `.aoc` in the workspace]
+    #[arg(long, env = "AOC_CACHE_DIR", value_name = "DIR")]
+    cache_dir: Option<PathBuf>,
+}
+
+impl SiteArgs {
+    fn client(&self) -> Result<Client, String> {
+        let session_file = match &self.session_file {
+            Some(path) => path.clone(),
+            None => env::var_os("HOME")
+                .map(|home| PathBuf::from(home).join(".config/aoc/session"))
+                .ok_or("HOME is not set, pass --session-file")?,
+        };
+        Client::new(&self.base_url, &session_file)
+    }
+
+    fn submission_log(&self) -> Result<SubmissionLog, String> {
+        let cache_dir = self
+            .cache_dir
+            .clone()
+            .unwrap_or_else(|| workspace_root().join(".aoc"));
+        SubmissionLog::load(&cache_dir.join("submissions.tsv"))
+    }
+}
+
+#[derive(Args)]
+struct AnswersArgs {
+    /// Answers accepted with `accept` [default: `answers.toml` in the workspace]
+    #[arg(long, env = "AOC_ANSWERS_FILE", value_name = "FILE")]
+    answers: Option<PathBuf>,
+}
+
+impl AnswersArgs {
+    fn store(&self) -> Result<AnswerStore, String> {
+        let path = self
+            .answers
+            .clone()
+            .unwrap_or_else(|| workspace_root().join("answers.toml"));
+        AnswerStore::load(&path)
+    }
+}
+
+/// How `--dump-parsed` writes the parsed input.
+#[cfg(feature = "serde")]
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum DumpFormat {
+    Json,
+}
+
+/// What `run` does with a day's input.
+#[derive(Clone, Copy)]
+enum Action {
+    /// Parse the puzzle input and solve it.
+    Solve(Format),
+    /// Parse the puzzle input and print the result.
+    #[cfg(feature = "serde")]
+    DumpParsed,
+    /// Load an input that was parsed before and solve it.
+    #[cfg(feature = "serde")]
+    SolveParsed(Format),
+}
+
+#[derive(Clone, Copy)]
+enum DaySelection {
+    All,
+    Day(u8),
+}
+
+impl FromStr for DaySelection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "all" {
+            return Ok(DaySelection::All);
+        }
+
+        match s.parse::<u8>() {
+            Ok(day) if days::find_day(day).is_some() => Ok(DaySelection::Day(day)),
+            Ok(day) => Err(format!("day {day} is not solved")),
+            Err(_) => Err(format!("expected a day number or `all`, got `{s}`")),
+        }
+    }
+}
+
+fn workspace_root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap()
+}
+
+/// A speed for `--fps`: any positive number of frames per second.
+fn parse_fps(value: &str) -> Result<f64, String> {
+    match value.parse::<f64>() {
+        Ok(fps) if fps.is_finite() && fps > 0.0 => Ok(fps),
+        _ => Err(format!("expected a positive number of frames per second, got `{value}`")),
+    }
+}
+
+/// `explicit` if given, otherwise the day's input from `$AOC_INPUT_DIR` or the workspace.
+fn input_source(day: u8, explicit: Option<InputSource>) -> InputSource {
+    InputSource::select(explicit, day, || {
+        workspace_root()
+            .join(format!("day{day:02}"))
+            .join("input")
+            .join("input.txt")
+    })
+}
+
+/// The day's parameters with `assignments` applied.
+fn day_params(entry: &days::Day, assignments: &[String]) -> Result<Params, String> {
+    let mut params = Params::new(entry.params);
+    for assignment in assignments {
+        params
+            .assign(assignment)
+            .map_err(|err| format!("day {}: {err}", entry.number))?;
+    }
+    Ok(params)
+}
+
+/// The day's solver, its parameters with `assignments` applied, and the text
+/// of its input.
+fn load_day_input(
+    day: u8,
+    assignments: &[String],
+    source: &InputSource,
+) -> Result<(&'static days::Day, Params, String), String> {
+    let entry = days::find_day(day).ok_or(format!("day {day} is not solved"))?;
+    let params = day_params(entry, assignments)?;
+    Ok((entry, params, read_input(day, source)?))
+}
+
+/// The text of the input in `source`; stdin can only be read once.
+fn read_input(day: u8, source: &InputSource) -> Result<String, String> {
+    source
+        .read()
+        .map_err(|err| format!("day {day}: cannot read {source}: {err}"))
+}
+
+/// A parse error in the input read from `source`, with the offending line.
+fn parse_failure(day: u8, source: &InputSource, input: &str, err: ParseError) -> String {
+    format!("day {day}: {source}: {err}\n{}", err.snippet(input))
+}
+
+fn run_day(
+    day: u8,
+    parts: &[Part],
+    source: &InputSource,
+    input: &str,
+    assignments: &[String],
+    action: Action,
+    answers: &AnswerStore,
+) -> Result<(), String> {
+    let entry = days::find_day(day).ok_or(format!("day {day} is not solved"))?;
+    let params = day_params(entry, assignments)?;
+    let _span = info_span!("day", day).entered();
+    let parse_error = |err| parse_failure(day, source, input, err);
+
+    let (report, format) = match action {
+        Action::Solve(format) => ((entry.solve)(input, &params, parts), format),
+        #[cfg(feature = "serde")]
+        Action::DumpParsed => {
+            let json = (entry.dump_parsed)(input, &params).map_err(parse_error)?;
+            println!("{json}");
+            return Ok(());
+        }
+        #[cfg(feature = "serde")]
+        Action::SolveParsed(format) => ((entry.solve_parsed)(input, parts), format),
+    };
+    let report = report.map_err(parse_error)?;
+    let checked = match action {
+        #[cfg(feature = "serde")]
+        Action::SolveParsed(_) => {
+            eprintln!("day {day}: answers to a parsed input are unchecked");
+            Ok(())
+        }
+        _ => answers.check(day, &input_key(input, &params), &report),
+    };
+    if format == Format::Json {
+        println!("{}", json_report(day, source, input, &report));
+        return checked;
+    }
+
+    println!(
+        "Day {day:02} parsed in {}",
+        format_duration(report.parse_elapsed)
+    );
+
+    for part in report.parts {
+        let elapsed = format_duration(part.elapsed);
+        match part.answer {
+            Ok(answer) => println!("Day {day:02} part {}: {answer} ({elapsed})", part.part),
+            Err(err) => println!("Day {day:02} part {}: {err}", part.part),
+        }
+    }
+
+    checked
+}
+
+/// Runs the selected days, and draws the picture of a single one to
+/// `picture` at the given scale, if asked to.
+fn run(
+    day: DaySelection,
+    part: Option<Part>,
+    input: Option<InputSource>,
+    params: &[String],
+    action: Action,
+    answers: &AnswerStore,
+    picture: Option<(&Path, u16)>,
+) -> Result<(), String> {
+    let parts = match part {
+        Some(part) => vec![part],
+        None => vec![Part::One, Part::Two],
+    };
+
+    match day {
+        DaySelection::Day(day) => {
+            let source = input_source(day, input);
+            let input = read_input(day, &source)?;
+            let result = run_day(day, &parts, &source, &input, params, action, answers);
+            // a wrong answer is no reason not to draw the input
+            match picture {
+                Some((path, scale)) => {
+                    let rendered = render(day, &source, &input, params, path, scale);
+                    result.and(rendered)
+                }
+                None => result,
+            }
+        }
+        DaySelection::All if picture.is_some() => {
+            Err("--render needs a single day".to_string())
+        }
+        DaySelection::All if !matches!(action, Action::Solve(_)) => {
+            Err("--dump-parsed and --parsed need a single day".to_string())
+        }
+        DaySelection::All if input.is_some() => Err(format!(
+            "--input cannot be combined with `all`, set {INPUT_DIR_VAR} instead"
+        )),
+        DaySelection::All if !params.is_empty() => {
+            Err("--param cannot be combined with `all`".to_string())
+        }
+        DaySelection::All => {
+            let mut failed = false;
+            for entry in days::DAYS {
+                let source = input_source(entry.number, None);
+                let result = read_input(entry.number, &source).and_then(|input| {
+                    run_day(entry.number, &parts, &source, &input, &[], action, answers)
+                });
+                if let Err(err) = result {
+                    eprintln!("{err}");
+                    failed = true;
+                }
+            }
+            if failed {
+                Err("some days could not be run".to_string())
+            } else {
+                Ok(())
+            }
+        }
+    }
+}
+
+fn render(
+    day: u8,
+    source: &InputSource,
+    input: &str,
+    assignments: &[String],
+    path: &Path,
+    scale: u16,
+) -> Result<(), String> {
+    let painter =
+        days::find_entry(days::PICTURES, day).ok_or(format!("day {day} has no picture"))?;
+    let entry = days::find_day(day).ok_or(format!("day {day} is not solved"))?;
+    let params = day_params(entry, assignments)?;
+
+    painter(input, &params, path, scale.into())
+        .map_err(|err| parse_failure(day, source, input, err))?
+        .map_err(|err| format!("day {day}: cannot write {}: {err}", path.display()))
+}
+
+fn accept(
+    day: u8,
+    part: Part,
+    input: Option<InputSource>,
+    assignments: &[String],
+    force: bool,
+    answers: &AnswersArgs,
+) -> Result<(), String> {
+    let source = input_source(day, input);
+    let (entry, params, input) = load_day_input(day, assignments, &source)?;
+    let report = (entry.solve)(&input, &params, &[part])
+        .map_err(|err| parse_failure(day, &source, &input, err))?;
+    let answer = match &report.parts[0].answer {
+        Ok(answer) => answer,
+        Err(err) => return Err(format!("day {day} part {part}: {err}, nothing to accept")),
+    };
+
+    let mut store = answers.store()?;
+    let key = input_key(&input, &params);
+    match store.get(day, &key, part) {
+        Some(accepted) if accepted == answer => {
+            println!("Day {day:02} part {part}: {answer} is accepted already");
+            return Ok(());
+        }
+        Some(accepted) if !force => {
+            return Err(format!(
+                "day {day} part {part}: {accepted} was accepted before, pass --force to replace it with {answer}"
+            ));
+        }
+        _ => {}
+    }
+
+    store.accept(day, &key, part, answer);
+    store.save()?;
+    println!("Day {day:02} part {part}: accepted {answer}");
+    Ok(())
+}
+
+fn animate(
+    day: u8,
+    input: Option<InputSource>,
+    assignments: &[String],
+    mut player: Player,
+) -> Result<(), String> {
+    let animator =
+        days::find_entry(days::ANIMATIONS, day).ok_or(format!("day {day} has no animation"))?;
+    let source = input_source(day, input);
+    let (_, params, input) = load_day_input(day, assignments, &source)?;
+
+    animator(&input, &params, &mut player)
+        .map_err(|err| parse_failure(day, &source, &input, err))?
+        .map_err(|err| format!("day {day}: terminal: {err}"))
+}
+
+fn debug(
+    day: u8,
+    input: Option<InputSource>,
+    assignments: &[String],
+    step: usize,
+) -> Result<(), String> {
+    let debugger =
+        days::find_entry(days::DEBUGGERS, day).ok_or(format!("day {day} has no debugger"))?;
+    let source = input_source(day, input);
+    let (_, params, input) = load_day_input(day, assignments, &source)?;
+
+    debugger(&input, &params, step)
+        .map_err(|err| parse_failure(day, &source, &input, err))?
+        .map_err(|err| format!("day {day}: terminal: {err}"))
+}
+
+fn new_day(day: u8) -> Result<(), String> {
+    let root = workspace_root();
+    for path in scaffold::new_day(root, day)? {
+        let path = path.strip_prefix(root).unwrap_or(&path);
+        println!("wrote {}", path.display());
+    }
+    Ok(())
+}
+
+fn fetch(day: u8, force: bool, site: &SiteArgs) -> Result<(), String> {
+    let InputSource::File(path) = input_source(day, None) else {
+        unreachable!("stdin is only used when given explicitly");
+    };
+
+    let is_cached = fs::metadata(&path).is_ok_and(|metadata| metadata.len() > 0);
+    if is_cached && !force {
+        println!("{} is already there", path.display());
+        return Ok(());
+    }
+    if !path.parent().is_some_and(Path::is_dir) {
+        return Err(format!(
+            "{} has no directory to go into, create the day with `aoc new {day}` or set {INPUT_DIR_VAR}",
+            path.display()
+        ));
+    }
+
+    let input = site.client()?.fetch_input(day)?;
+    fs::write(&path, input).map_err(|err| format!("cannot write {}: {err}", path.display()))?;
+    println!("wrote {}", path.display());
+    Ok(())
+}
+
+fn submit(day: u8, part: Part, answer: &str, site: &SiteArgs) -> Result<(), String> {
+    let answer = answer.trim();
+    if answer.is_empty() || answer.contains(char::is_whitespace) {
+        return Err(format!("`{answer}` does not look like an answer"));
+    }
+
+    let mut log = site.submission_log()?;
+    log.check(day, part, answer)
+        .map_err(|err| format!("day {day} part {part}: not submitting, {err}"))?;
+
+    let (verdict, message) = site.client()?.submit(day, part, answer)?;
+    log.record(Submission {
+        day,
+        part,
+        answer: answer.to_string(),
+        verdict,
+        message: message.clone(),
+    })?;
+
+    println!("{message}");
+    match verdict {
+        Verdict::Correct => Ok(()),
+        _ => Err(format!(
+            "day {day} part {part}: {answer} was not accepted ({verdict})"
+        )),
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    init_logging(cli.verbose);
+    if let Some(jobs) = cli.jobs {
+        if let Err(err) = set_jobs(jobs.into()) {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    }
+
+    let result = match cli.command {
+        Command::Run {
+            day,
+            part,
+            input,
+            params,
+            format,
+            #[cfg(feature = "serde")]
+            dump_parsed,
+            #[cfg(feature = "serde")]
+            parsed,
+            render: picture,
+            scale,
+            answers,
+        } => {
+            let action = Action::Solve(format);
+            #[cfg(feature = "serde")]
+            let (input, action) = match (dump_parsed, parsed) {
+                (Some(DumpFormat::Json), _) => (input, Action::DumpParsed),
+                (None, Some(parsed)) => (Some(parsed), Action::SolveParsed(format)),
+                (None, None) => (input, action),
+            };
+            let picture = picture.as_deref().map(|path| (path, scale));
+            answers
+                .store()
+                .and_then(|answers| run(day, part, input, &params, action, &answers, picture))
+        }
+        Command::Accept {
+            day,
+            part,
+            input,
+            params,
+            force,
+            answers,
+        } => accept(day, part, input, &params, force, &answers),
+        Command::Animate {
+            day,
+            input,
+            params,
+            fps,
+            paused,
+        } => animate(day, input, &params, Player::new().fps(fps).paused(paused)),
+        Command::Debug {
+            day,
+            input,
+            params,
+            step,
+        } => debug(day, input, &params, step),
+        Command::New { day } => new_day(day),
+        Command::Fetch { day, force, site } => fetch(day, force, &site),
+        Command::Submit {
+            day,
+            part,
+            answer,
+            site,
+        } => submit(day, part, &answer, &site),
+    };
+
+    if let Err(err) = result {
+        eprintln!("{err}");
+        return ExitCode::FAILURE;
+    }
+
+    ExitCode::SUCCESS
+}
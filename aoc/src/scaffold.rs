@@ -0,0 +1,226 @@
+//! `aoc new`: creates a `dayNN` crate from the `dayXX` template and wires it
+//! into the workspace, the runner, and the example and round-trip tests.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Template files, relative to the crate directory.
+const TEMPLATE: &[(&str, &str)] = &[
+    ("Cargo.toml", include_str!("../../dayXX/Cargo.toml")),
+    ("src/lib.rs", include_str!("../../dayXX/src/lib.rs")),
+    ("src/main.rs", include_str!("../../dayXX/src/main.rs")),
+    (
+        "input/input.txt",
+        include_str!("../../dayXX/input/input.txt"),
+    ),
+    (
+        "input/test_input.txt",
+        include_str!("../../dayXX/input/test_input.txt"),
+    ),
+    (
+        "input/test_answers.txt",
+        include_str!("../../dayXX/input/test_answers.txt"),
+    ),
+];
+
+/// The template stays outside of the workspace, so it cannot inherit its lints.
+const WORKSPACE_LINTS: &str = "\n[lints]\nworkspace = true\n";
+
+fn render(file: &str, template: &str, day: u8) -> String {
+    let text = template
+        .replace("run_main::<DayXX>(0)", &format!("run_main::<DayXX>({day})"))
+        .replace("dayXX", &format!("day{day:02}"))
+        .replace("DayXX", &format!("Day{day:02}"));
+
+    match file {
+        "Cargo.toml" => text + WORKSPACE_LINTS,
+        _ => text,
+    }
+}
+
+/// Day number in the two digits right after `prefix` at the start of `line`.
+fn day_after(line: &str, prefix: &str) -> Option<u8> {
+    let digits = line.trim_start().strip_prefix(prefix)?.get(..2)?;
+    if !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse().ok()
+}
+
+/// Inserts `new_line` among the lines belonging to a day, keeping them
+/// sorted; `day_of` tells which lines those are.
+fn insert_line(
+    content: &str,
+    day: u8,
+    new_line: &str,
+    day_of: impl Fn(&str) -> Option<u8>,
+) -> Result<String, String> {
+    let mut lines: Vec<&str> = content.lines().collect();
+    let entries: Vec<(usize, u8)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, line)| day_of(line).map(|day| (idx, day)))
+        .collect();
+
+    if entries.iter().any(|(_, other)| *other == day) {
+        return Err(format!("day {day} is already listed"));
+    }
+    let idx = match entries.iter().find(|(_, other)| *other > day) {
+        Some((idx, _)) => *idx,
+        None => entries.last().ok_or("no days are listed")?.0 + 1,
+    };
+
+    lines.insert(idx, new_line);
+    Ok(lines.join("\n") + "\n")
+}
+
+struct Edit {
+    path: &'static str,
+    new_line: String,
+    prefix: &'static str,
+}
+
+/// Creates `dayNN/` under the workspace `root` and registers it everywhere,
+/// returning the files written. Nothing is written if any step would fail.
+pub fn new_day(root: &Path, day: u8) -> Result<Vec<PathBuf>, String> {
+    let name = format!("day{day:02}");
+    let crate_dir = root.join(&name);
+    if crate_dir.exists() {
+        return Err(format!("{} already exists", crate_dir.display()));
+    }
+
+    let edits = [
+        Edit {
+            path: "Cargo.toml",
+            new_line: format!("    \"{name}\","),
+            prefix: "\"day",
+        },
+        Edit {
+            path: "aoc/Cargo.toml",
+            new_line: format!("{name} = {{ path = \"../{name}\" }}"),
+            prefix: "day",
+        },
+        Edit {
+            path: "aoc/src/days.rs",
+            new_line: format!("    Day::new::<{name}::Day{day:02}>({day}),"),
+            prefix: "Day::new::<day",
+        },
+        Edit {
+            path: "aoc/tests/examples.rs",
+            new_line: format!("    {name}: {name}::Day{day:02},"),
+            prefix: "day",
+        },
+        Edit {
+            path: "aoc/tests/parsed.rs",
+            new_line: format!("    {name}: {name}::Day{day:02},"),
+            prefix: "day",
+        },
+    ];
+
+    // prepare every change first, so that a failure leaves the tree untouched
+    let mut writes = Vec::new();
+    for edit in &edits {
+        let path = root.join(edit.path);
+        let content = fs::read_to_string(&path)
+            .map_err(|err| format!("cannot read {}: {err}", path.display()))?;
+        let content = insert_line(&content, day, &edit.new_line, |line| {
+            day_after(line, edit.prefix)
+        })
+        .map_err(|err| format!("{}: {err}", path.display()))?;
+        writes.push((path, content));
+    }
+    for (file, template) in TEMPLATE {
+        writes.push((crate_dir.join(file), render(file, template, day)));
+    }
+
+    for (path, content) in &writes {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|err| format!("{}: {err}", dir.display()))?;
+        }
+        fs::write(path, content).map_err(|err| format!("{}: {err}", path.display()))?;
+    }
+
+    Ok(writes.into_iter().map(|(path, _)| path).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_line_test() {
+        let content = "[x]\n  day01 = 1\n  day03 = 3\nother\n";
+        let day_of = |line: &str| day_after(line, "day");
+
+        assert_eq!(
+            insert_line(content, 2, "  day02 = 2", day_of).unwrap(),
+            "[x]\n  day01 = 1\n  day02 = 2\n  day03 = 3\nother\n"
+        );
+        assert_eq!(
+            insert_line(content, 20, "  day20 = 20", day_of).unwrap(),
+            "[x]\n  day01 = 1\n  day03 = 3\n  day20 = 20\nother\n"
+        );
+        assert!(insert_line(content, 3, "  day03 = 3", day_of).is_err());
+        assert!(insert_line("[x]\n", 3, "  day03 = 3", day_of).is_err());
+        assert_eq!(day_after("exclude = [\"dayXX\"]", "\"day"), None);
+    }
+
+    #[test]
+    fn new_day_test() {
+        let root = std::env::temp_dir().join(format!("aoc-new-day-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("aoc/src")).unwrap();
+        fs::create_dir_all(root.join("aoc/tests")).unwrap();
+        fs::write(
+            root.join("Cargo.toml"),
+            "members = [\n    \"day01\",\n    \"day25\",\n]\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("aoc/Cargo.toml"),
+            "day01 = { path = \"../day01\" }\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("aoc/src/days.rs"),
+            "    Day::new::<day01::Day01>(1),\n",
+        )
+        .unwrap();
+        for tests in ["examples.rs", "parsed.rs"] {
+            fs::write(
+                root.join("aoc/tests").join(tests),
+                "    day01: day01::Day01,\n",
+            )
+            .unwrap();
+        }
+
+        new_day(&root, 7).unwrap();
+
+        let main = fs::read_to_string(root.join("day07/src/main.rs")).unwrap();
+        assert!(main.contains("run_main::<Day07>(7)"));
+        let manifest = fs::read_to_string(root.join("day07/Cargo.toml")).unwrap();
+        assert!(manifest.contains("name = \"day07\""));
+        assert!(manifest.ends_with(WORKSPACE_LINTS));
+        assert_eq!(
+            fs::read_to_string(root.join("Cargo.toml")).unwrap(),
+            "members = [\n    \"day01\",\n    \"day07\",\n    \"day25\",\n]\n"
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("aoc/tests/parsed.rs")).unwrap(),
+            "    day01: day01::Day01,\n    day07: day07::Day07,\n"
+        );
+        assert!(root.join("day07/input/test_input.txt").exists());
+
+        // an existing day is never overwritten
+        fs::write(root.join("day07/src/lib.rs"), "solved").unwrap();
+        assert!(new_day(&root, 7).is_err());
+        assert_eq!(
+            fs::read_to_string(root.join("day07/src/lib.rs")).unwrap(),
+            "solved"
+        );
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
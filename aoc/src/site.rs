@@ -0,0 +1,211 @@
+//! Client for the puzzle site: downloads inputs and submits answers.
+
+use std::{fmt, fs, path::Path, str::FromStr, time::Duration};
+
+use aoc_common::Part;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const YEAR: u16 = 2024;
+
+const USER_AGENT: &str = concat!("aoc-runner/", env!("CARGO_PKG_VERSION"), " (ureq)");
+
+/// What the site made of a submitted answer.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// Submitted before the cooldown after a wrong answer ran out.
+    TooSoon,
+    /// The part is locked or already solved.
+    WrongLevel,
+    Unknown,
+}
+
+impl Verdict {
+    /// Recognises the verdict in the text of an answer page.
+    pub fn classify(message: &str) -> Verdict {
+        let message = message.to_lowercase();
+        if message.contains("that's the right answer") {
+            Verdict::Correct
+        } else if message.contains("too high") {
+            Verdict::TooHigh
+        } else if message.contains("too low") {
+            Verdict::TooLow
+        } else if message.contains("not the right answer") {
+            Verdict::Wrong
+        } else if message.contains("answer too recently") {
+            Verdict::TooSoon
+        } else if message.contains("right level") {
+            Verdict::WrongLevel
+        } else {
+            Verdict::Unknown
+        }
+    }
+
+    /// Whether the answer itself was judged wrong, as opposed to not judged at all.
+    pub fn is_wrong(self) -> bool {
+        matches!(self, Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong)
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too-high",
+            Verdict::TooLow => "too-low",
+            Verdict::Wrong => "wrong",
+            Verdict::TooSoon => "too-soon",
+            Verdict::WrongLevel => "wrong-level",
+            Verdict::Unknown => "unknown",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "too-high" => Ok(Verdict::TooHigh),
+            "too-low" => Ok(Verdict::TooLow),
+            "wrong" => Ok(Verdict::Wrong),
+            "too-soon" => Ok(Verdict::TooSoon),
+            "wrong-level" => Ok(Verdict::WrongLevel),
+            "unknown" => Ok(Verdict::Unknown),
+            _ => Err(format!("unknown verdict `{s}`")),
+        }
+    }
+}
+
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    /// Client for the site at `base_url`, logged in with the session cookie
+    /// stored in `session_file`.
+    pub fn new(base_url: &str, session_file: &Path) -> Result<Client, String> {
+        let session = fs::read_to_string(session_file).map_err(|err| {
+            format!(
+                "cannot read the session token from {}: {err}\n\
+                 save the value of the site's `session` cookie there",
+                session_file.display()
+            )
+        })?;
+
+        Ok(Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+        })
+    }
+
+    fn day_url(&self, day: u8, page: &str) -> String {
+        format!("{}/{YEAR}/day/{day}/{page}", self.base_url)
+    }
+
+    pub fn fetch_input(&self, day: u8) -> Result<String, String> {
+        let url = self.day_url(day, "input");
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call()
+            .map_err(|err| describe_error(&url, err))?;
+
+        response
+            .into_string()
+            .map_err(|err| format!("{url}: {err}"))
+    }
+
+    /// Submits `answer` and returns the verdict with the site's message.
+    pub fn submit(&self, day: u8, part: Part, answer: &str) -> Result<(Verdict, String), String> {
+        let url = self.day_url(day, "answer");
+        let level = part.to_string();
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &level), ("answer", answer)])
+            .map_err(|err| describe_error(&url, err))?;
+
+        let page = response
+            .into_string()
+            .map_err(|err| format!("{url}: {err}"))?;
+        let message = article_text(&page);
+        Ok((Verdict::classify(&message), message))
+    }
+}
+
+fn describe_error(url: &str, err: ureq::Error) -> String {
+    match err {
+        ureq::Error::Status(400, _) => {
+            format!("{url}: the site did not accept the session token, it may have expired")
+        }
+        ureq::Error::Status(404, _) => format!("{url}: not found, the puzzle may be locked"),
+        ureq::Error::Status(code, response) => {
+            format!("{url}: {code} {}", response.status_text())
+        }
+        ureq::Error::Transport(err) => format!("{url}: {err}"),
+    }
+}
+
+/// Plain text of the page's `<article>`, where the site explains its verdict.
+fn article_text(page: &str) -> String {
+    let article = match (page.find("<article"), page.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &page[start..end],
+        _ => page,
+    };
+
+    let mut text = String::new();
+    let mut in_tag = false;
+    for chr in article.chars() {
+        match chr {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(chr),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verdict_test() {
+        let page = "<main>\n<article><p>That's not the right answer; your answer is too high. \
+                    Please wait one minute before trying again. <a href=\"/2024/day/1\">[Return to \
+                    Day 1]</a></p></article>\n</main>";
+        let message = article_text(page);
+        assert!(message.starts_with("That's not the right answer; your answer is too high."));
+        assert!(message.ends_with("[Return to Day 1]"));
+        assert_eq!(Verdict::classify(&message), Verdict::TooHigh);
+
+        assert_eq!(
+            Verdict::classify("That's the right answer! You are one gold star closer."),
+            Verdict::Correct
+        );
+        assert_eq!(
+            Verdict::classify("You gave an answer too recently; you have 42s left to wait."),
+            Verdict::TooSoon
+        );
+        assert_eq!(
+            Verdict::classify("You don't seem to be solving the right level."),
+            Verdict::WrongLevel
+        );
+        assert_eq!("too-low".parse(), Ok(Verdict::TooLow));
+    }
+}
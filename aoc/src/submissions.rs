@@ -0,0 +1,173 @@
+//! Record of submitted answers and the site's verdicts, kept so that an
+//! answer known to be wrong is never sent twice.
+
+use std::{
+    fs::{self, OpenOptions},
+    io::{ErrorKind, Write},
+    path::{Path, PathBuf},
+};
+
+use aoc_common::Part;
+
+use crate::site::Verdict;
+
+const HEADER: &str = "# day\tpart\tverdict\tanswer\tmessage\n";
+
+pub struct Submission {
+    pub day: u8,
+    pub part: Part,
+    pub answer: String,
+    pub verdict: Verdict,
+    pub message: String,
+}
+
+pub struct SubmissionLog {
+    path: PathBuf,
+    submissions: Vec<Submission>,
+}
+
+impl SubmissionLog {
+    /// Reads the log at `path`; a missing file is an empty log.
+    pub fn load(path: &Path) -> Result<SubmissionLog, String> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(format!("cannot read {}: {err}", path.display())),
+        };
+
+        let submissions = content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+            .map(|(idx, line)| {
+                parse_line(line)
+                    .ok_or_else(|| format!("{}:{}: malformed submission", path.display(), idx + 1))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(SubmissionLog {
+            path: path.to_path_buf(),
+            submissions,
+        })
+    }
+
+    /// Refuses answers that are certain to be rejected: ones rejected before,
+    /// ones on the wrong side of a "too high" or "too low", and any answer to
+    /// a part that is already solved.
+    pub fn check(&self, day: u8, part: Part, answer: &str) -> Result<(), String> {
+        let previous = self
+            .submissions
+            .iter()
+            .filter(|submission| submission.day == day && submission.part == part);
+
+        for submission in previous {
+            let known = &submission.answer;
+            match submission.verdict {
+                Verdict::Correct if known == answer => {
+                    return Err(format!("{answer} was already accepted"));
+                }
+                Verdict::Correct => {
+                    return Err(format!("this part is already solved, with {known}"));
+                }
+                verdict if verdict.is_wrong() && known == answer => {
+                    return Err(format!("{answer} was already rejected as {verdict}"));
+                }
+                Verdict::TooHigh if is_at_least(answer, known) => {
+                    return Err(format!("{answer} is not below {known}, which was too high"));
+                }
+                Verdict::TooLow if is_at_least(known, answer) => {
+                    return Err(format!("{answer} is not above {known}, which was too low"));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn record(&mut self, submission: Submission) -> Result<(), String> {
+        let write = || -> std::io::Result<()> {
+            if let Some(dir) = self.path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            let mut file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.path)?;
+            if file.metadata()?.len() == 0 {
+                file.write_all(HEADER.as_bytes())?;
+            }
+            writeln!(
+                file,
+                "{}\t{}\t{}\t{}\t{}",
+                submission.day,
+                submission.part,
+                submission.verdict,
+                submission.answer,
+                submission.message.replace(['\t', '\n'], " ")
+            )
+        };
+        write().map_err(|err| format!("cannot write {}: {err}", self.path.display()))?;
+
+        self.submissions.push(submission);
+        Ok(())
+    }
+}
+
+fn parse_line(line: &str) -> Option<Submission> {
+    let mut fields = line.splitn(5, '\t');
+    Some(Submission {
+        day: fields.next()?.parse().ok()?,
+        part: fields.next()?.parse().ok()?,
+        verdict: fields.next()?.parse().ok()?,
+        answer: fields.next()?.to_string(),
+        message: fields.next().unwrap_or_default().to_string(),
+    })
+}
+
+/// `left >= right` when both are integers.
+fn is_at_least(left: &str, right: &str) -> bool {
+    match (left.parse::<i128>(), right.parse::<i128>()) {
+        (Ok(left), Ok(right)) => left >= right,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn submission(part: Part, answer: &str, verdict: Verdict) -> Submission {
+        Submission {
+            day: 3,
+            part,
+            answer: answer.to_string(),
+            verdict,
+            message: String::new(),
+        }
+    }
+
+    #[test]
+    fn check_test() {
+        let log = SubmissionLog {
+            path: PathBuf::new(),
+            submissions: vec![
+                submission(Part::One, "500", Verdict::TooHigh),
+                submission(Part::One, "100", Verdict::TooLow),
+                submission(Part::One, "250", Verdict::Wrong),
+                submission(Part::One, "260", Verdict::TooSoon),
+                submission(Part::Two, "42", Verdict::Correct),
+            ],
+        };
+
+        assert!(log.check(3, Part::One, "300").is_ok());
+        assert!(log.check(3, Part::One, "260").is_ok());
+        assert!(log.check(3, Part::One, "250").is_err());
+        assert!(log.check(3, Part::One, "500").is_err());
+        assert!(log.check(3, Part::One, "501").is_err());
+        assert!(log.check(3, Part::One, "99").is_err());
+        assert!(log.check(3, Part::Two, "42").is_err());
+        assert!(log.check(3, Part::Two, "43").is_err());
+        assert!(log.check(4, Part::One, "500").is_ok());
+    }
+}
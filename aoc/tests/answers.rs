@@ -0,0 +1,81 @@
+//! `aoc accept` followed by `aoc run` on day 14's example, which also needs
+//! its parameters to match.
+
+use std::{
+    fs,
+    path::PathBuf,
+    process::{Command, Output},
+};
+
+fn aoc(answers: &PathBuf, args: &[&str]) -> Output {
+    let input = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("day14")
+        .join("input")
+        .join("test_input.txt");
+    Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(args)
+        .arg("--input")
+        .arg(input)
+        .env("AOC_ANSWERS_FILE", answers)
+        .output()
+        .unwrap()
+}
+
+/// `args` with the parameters of the example.
+fn example<'a>(args: &[&'a str]) -> Vec<&'a str> {
+    [args, &["--param", "width=11", "--param", "height=7"]].concat()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+#[test]
+fn accept_test() {
+    let dir = std::env::temp_dir().join(format!("aoc-accept-{}", std::process::id()));
+    let answers = dir.join("answers.toml");
+
+    let output = aoc(&answers, &example(&["accept", "14", "1"]));
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(fs::read_to_string(&answers)
+        .unwrap()
+        .contains("part1 = \"12\""));
+
+    let output = aoc(&answers, &example(&["run", "14"]));
+    assert!(output.status.success(), "{}", stderr(&output));
+    // other parameters make for other answers, which were never accepted
+    let output = aoc(&answers, &["run", "14", "--param", "width=13"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    // as if a change to the solver made it answer something else
+    let content = fs::read_to_string(&answers).unwrap();
+    fs::write(&answers, content.replace("\"12\"", "\"13\"")).unwrap();
+    let output = aoc(&answers, &example(&["run", "14"]));
+    assert!(!output.status.success());
+    assert!(
+        stderr(&output).contains("day 14 part 1: got 12, but 13 was accepted for this input"),
+        "{}",
+        stderr(&output)
+    );
+    // the picture is drawn all the same
+    let picture = dir.join("day14.ppm");
+    let render = ["run", "14", "--render", picture.to_str().unwrap()];
+    let output = aoc(&answers, &example(&render));
+    assert!(!output.status.success());
+    assert!(picture.exists(), "{}", stderr(&output));
+
+    let output = aoc(&answers, &example(&["accept", "14", "1"]));
+    assert!(!output.status.success());
+    assert!(
+        stderr(&output).contains("pass --force"),
+        "{}",
+        stderr(&output)
+    );
+    let output = aoc(&answers, &example(&["accept", "14", "1", "--force"]));
+    assert!(output.status.success(), "{}", stderr(&output));
+    let output = aoc(&answers, &example(&["run", "14"]));
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,148 @@
+//! Runs every day against its example inputs. The expected answers live next
+//! to the examples, in `dayNN/input/test_answers.txt`: one line per input
+//! file with the file name and the answers to part 1 and part 2, `-` leaving
+//! a part unchecked, followed by any `name=value` puzzle parameters the
+//! example needs. Every `test_input*.txt` must have a line there.
+
+use std::{fs, path::PathBuf};
+
+use aoc_common::{Params, Solution};
+
+const ANSWERS_FILE: &str = "test_answers.txt";
+
+struct Fixture {
+    file: String,
+    expected: [Option<String>; 2],
+    params: Vec<String>,
+}
+
+fn input_dir(day: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join(day)
+        .join("input")
+}
+
+fn read_fixtures(day: &str) -> Vec<Fixture> {
+    let path = input_dir(day).join(ANSWERS_FILE);
+    let content =
+        fs::read_to_string(&path).unwrap_or_else(|err| panic!("{}: {err}", path.display()));
+
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+        .map(|(idx, line)| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let [file, part1, part2, ref params @ ..] = fields[..] else {
+                panic!(
+                    "{}:{}: expected `<file> <part 1> <part 2> [<name>=<value>...]`",
+                    path.display(),
+                    idx + 1
+                );
+            };
+            let expected = |answer: &str| (answer != "-").then(|| answer.to_string());
+
+            Fixture {
+                file: file.to_string(),
+                expected: [expected(part1), expected(part2)],
+                params: params.iter().map(|param| param.to_string()).collect(),
+            }
+        })
+        .collect()
+}
+
+fn check<S: Solution>(day: &str) {
+    let dir = input_dir(day);
+    let fixtures = read_fixtures(day);
+    let mut failures = Vec::new();
+
+    for entry in fs::read_dir(&dir).unwrap() {
+        let name = entry.unwrap().file_name().to_string_lossy().into_owned();
+        let is_example = name.starts_with("test_input") && name.ends_with(".txt");
+        if is_example && !fixtures.iter().any(|fixture| fixture.file == name) {
+            failures.push(format!("{name}: no answers in {ANSWERS_FILE}"));
+        }
+    }
+
+    for fixture in &fixtures {
+        let path = dir.join(&fixture.file);
+        let input =
+            fs::read_to_string(&path).unwrap_or_else(|err| panic!("{}: {err}", path.display()));
+
+        let mut parsed = match S::parse(&input) {
+            Ok(parsed) => parsed,
+            Err(err) => {
+                failures.push(format!("{}: {err}\n{}", fixture.file, err.snippet(&input)));
+                continue;
+            }
+        };
+
+        let mut params = Params::new(S::PARAMS);
+        for assignment in &fixture.params {
+            if let Err(err) = params.assign(assignment) {
+                failures.push(format!("{}: {err}", fixture.file));
+            }
+        }
+        S::configure(&mut parsed, &params);
+
+        let answers = [
+            S::part1(&parsed).map(|answer| answer.to_string()),
+            S::part2(&parsed).map(|answer| answer.to_string()),
+        ];
+
+        for (part, (expected, answer)) in fixture.expected.iter().zip(answers).enumerate() {
+            let Some(expected) = expected else {
+                continue;
+            };
+            match answer {
+                Ok(answer) if answer == *expected => {}
+                Ok(answer) => failures.push(format!(
+                    "{}: part {}: expected {expected}, got {answer}",
+                    fixture.file,
+                    part + 1
+                )),
+                Err(err) => failures.push(format!(
+                    "{}: part {}: expected {expected}, got error: {err}",
+                    fixture.file,
+                    part + 1
+                )),
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "{day}:\n{}", failures.join("\n"));
+}
+
+/// One test per day, named after its crate.
+macro_rules! examples {
+    ($($day:ident: $solution:ty,)*) => {
+        $(
+            #[test]
+            fn $day() {
+                check::<$solution>(stringify!($day));
+            }
+        )*
+    };
+}
+
+examples! {
+    day01: day01::Day01,
+    day02: day02::Day02,
+    day03: day03::Day03,
+    day04: day04::Day04,
+    day05: day05::Day05,
+    day06: day06::Day06,
+    day07: day07::Day07,
+    day08: day08::Day08,
+    day09: day09::Day09,
+    day10: day10::Day10,
+    day11: day11::Day11,
+    day12: day12::Day12,
+    day13: day13::Day13,
+    day14: day14::Day14,
+    day15: day15::Day15,
+    day16: day16::Day16,
+    day18: day18::Day18,
+    day19: day19::Day19,
+}
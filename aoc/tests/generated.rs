@@ -0,0 +1,69 @@
+//! Runs every day on small random inputs from `aoc_gen`: each of them must
+//! parse, and solving must not panic. Failures shrink towards the smallest
+//! input that still fails.
+
+use aoc_common::{solve, Params, Part, Solution, SolveError};
+use proptest::prelude::*;
+
+/// Parses `input` and solves both parts, keeping only the answers.
+fn answers<S: Solution>(input: &str) -> [Result<String, SolveError>; 2] {
+    let params = Params::new(S::PARAMS);
+    let report = solve::<S>(input, &params, &[Part::One, Part::Two])
+        .unwrap_or_else(|err| panic!("{err}\n{}", err.snippet(input)));
+    let [part1, part2] = <[_; 2]>::try_from(report.parts).ok().unwrap();
+    [part1.answer, part2.answer]
+}
+
+/// One property test per `dayNN: DayNN` with input sizes in the given range.
+macro_rules! generated {
+    ($($day:ident: $solution:ty = $sizes:expr,)*) => {
+        proptest! {
+            #![proptest_config(ProptestConfig::with_cases(16))]
+
+            $(
+                #[test]
+                fn $day(input in aoc_gen::inputs(aoc_gen::$day::input, $sizes)) {
+                    // any answer will do, as long as there is one
+                    let _ = answers::<$solution>(&input);
+                }
+            )*
+        }
+    };
+}
+
+generated! {
+    day01: day01::Day01 = 1..=50,
+    day02: day02::Day02 = 1..=50,
+    day03: day03::Day03 = 1..=500,
+    day04: day04::Day04 = 1..=20,
+    day05: day05::Day05 = 1..=20,
+    day06: day06::Day06 = 1..=20,
+    day07: day07::Day07 = 1..=20,
+    day08: day08::Day08 = 1..=20,
+    day09: day09::Day09 = 1..=200,
+    day10: day10::Day10 = 1..=20,
+    day11: day11::Day11 = 1..=4,
+    day12: day12::Day12 = 1..=20,
+    day14: day14::Day14 = 1..=50,
+    day15: day15::Day15 = 3..=12,
+    day16: day16::Day16 = 3..=31,
+    day18: day18::Day18 = 1..=12,
+    day19: day19::Day19 = 1..=30,
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(16))]
+
+    #[test]
+    fn day13(input in aoc_gen::day13::machines(1..=20)) {
+        let _ = answers::<day13::Day13>(&input);
+    }
+
+    /// The generated mazes always connect `S` and `E`.
+    #[test]
+    fn day16_has_a_path(input in aoc_gen::inputs(aoc_gen::day16::input, 3..=31)) {
+        let [part1, part2] = answers::<day16::Day16>(&input);
+        prop_assert!(part1.is_ok());
+        prop_assert!(part2.is_ok());
+    }
+}
@@ -0,0 +1,194 @@
+//! Local stand-in for the puzzle site: serves inputs and judges answers the
+//! way the real site words it, for a single session token.
+
+use std::{
+    collections::HashMap,
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    sync::{Arc, Mutex},
+    thread,
+};
+
+#[derive(Default)]
+struct State {
+    inputs: HashMap<u8, String>,
+    answers: HashMap<(u8, u8), String>,
+    /// `METHOD path` of every request served.
+    requests: Vec<String>,
+}
+
+pub struct MockServer {
+    pub url: String,
+    session: String,
+    state: Arc<Mutex<State>>,
+}
+
+struct Request {
+    method: String,
+    path: String,
+    cookie: Option<String>,
+    body: String,
+}
+
+impl MockServer {
+    /// Starts serving on a free local port, accepting only `session`.
+    pub fn start(session: &str) -> MockServer {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = MockServer {
+            url,
+            session: session.to_string(),
+            state: Arc::default(),
+        };
+
+        let state = server.state.clone();
+        let session = server.session.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(stream) = stream else { continue };
+                handle(stream, &session, &state);
+            }
+        });
+
+        server
+    }
+
+    pub fn add_day(&self, day: u8, input: &str, answers: [&str; 2]) {
+        let mut state = self.state.lock().unwrap();
+        state.inputs.insert(day, input.to_string());
+        state.answers.insert((day, 1), answers[0].to_string());
+        state.answers.insert((day, 2), answers[1].to_string());
+    }
+
+    pub fn requests(&self) -> Vec<String> {
+        self.state.lock().unwrap().requests.clone()
+    }
+}
+
+fn read_request(stream: &mut TcpStream) -> Option<Request> {
+    let mut reader = BufReader::new(stream);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).ok()?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next()?.to_string();
+    let path = parts.next()?.to_string();
+
+    let mut cookie = None;
+    let mut content_length = 0;
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).ok()?;
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        let (name, value) = line.split_once(':')?;
+        match name.to_ascii_lowercase().as_str() {
+            "cookie" => cookie = Some(value.trim().to_string()),
+            "content-length" => content_length = value.trim().parse().ok()?,
+            _ => {}
+        }
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).ok()?;
+
+    Some(Request {
+        method,
+        path,
+        cookie,
+        body: String::from_utf8(body).ok()?,
+    })
+}
+
+fn handle(mut stream: TcpStream, session: &str, state: &Mutex<State>) {
+    let Some(request) = read_request(&mut stream) else {
+        return;
+    };
+    let mut state = state.lock().unwrap();
+    state
+        .requests
+        .push(format!("{} {}", request.method, request.path));
+
+    let (status, body) = if request.cookie != Some(format!("session={session}")) {
+        (
+            "400 Bad Request",
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n".to_string(),
+        )
+    } else {
+        respond(&request, &state)
+    };
+
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    let _ = stream.write_all(response.as_bytes());
+}
+
+fn respond(request: &Request, state: &State) -> (&'static str, String) {
+    let segments: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+    let day = match segments[..] {
+        [_year, "day", day, _page] => day.parse::<u8>().ok(),
+        _ => None,
+    };
+    let Some(day) = day.filter(|day| state.inputs.contains_key(day)) else {
+        return ("404 Not Found", "404 Not Found\n".to_string());
+    };
+
+    match (request.method.as_str(), segments[3]) {
+        ("GET", "input") => ("200 OK", state.inputs[&day].clone()),
+        ("POST", "answer") => {
+            let form: HashMap<String, String> = request
+                .body
+                .split('&')
+                .filter_map(|pair| pair.split_once('='))
+                .map(|(key, value)| (decode(key), decode(value)))
+                .collect();
+            let level = form.get("level").and_then(|level| level.parse().ok());
+            let (Some(level), Some(answer)) = (level, form.get("answer")) else {
+                return ("400 Bad Request", "Bad Request\n".to_string());
+            };
+            ("200 OK", judge(&state.answers[&(day, level)], answer))
+        }
+        _ => ("404 Not Found", "404 Not Found\n".to_string()),
+    }
+}
+
+fn judge(expected: &str, answer: &str) -> String {
+    let message = if answer == expected {
+        "That's the right answer!  You are one gold star closer to finding the Chief Historian."
+            .to_string()
+    } else {
+        let hint = match (answer.parse::<i64>(), expected.parse::<i64>()) {
+            (Ok(answer), Ok(expected)) if answer > expected => " your answer is too high.",
+            (Ok(answer), Ok(expected)) if answer < expected => " your answer is too low.",
+            _ => "",
+        };
+        format!(
+            "That's not the right answer;{hint}  If you're stuck, make sure you're using the \
+             full input data.  Please wait one minute before trying again."
+        )
+    };
+
+    format!("<html><body><main>\n<article><p>{message}</p></article>\n</main></body></html>\n")
+}
+
+/// Decodes `application/x-www-form-urlencoded` text.
+fn decode(text: &str) -> String {
+    let mut bytes = Vec::new();
+    let mut iter = text.bytes();
+    while let Some(byte) = iter.next() {
+        match byte {
+            b'+' => bytes.push(b' '),
+            b'%' => {
+                let hex = [iter.next().unwrap_or(b'0'), iter.next().unwrap_or(b'0')];
+                let hex = std::str::from_utf8(&hex).unwrap_or("00");
+                bytes.push(u8::from_str_radix(hex, 16).unwrap_or(b'?'));
+            }
+            _ => bytes.push(byte),
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
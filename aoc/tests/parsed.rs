@@ -0,0 +1,80 @@
+//! Writes every day's example out with `dump_parsed` and solves it again from
+//! there: the answers must not change on the way.
+
+#![cfg(feature = "serde")]
+
+use std::{fs, path::PathBuf};
+
+use aoc_common::{dump_parsed, solve, solve_parsed, Params, Part, Report, Solution};
+use serde::{de::DeserializeOwned, Serialize};
+
+fn round_trip<S: Solution>(day: &str)
+where
+    S::Input: Serialize + DeserializeOwned,
+{
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join(day)
+        .join("input")
+        .join("test_input.txt");
+    let input = fs::read_to_string(&path).unwrap_or_else(|err| panic!("{}: {err}", path.display()));
+    let params = Params::new(S::PARAMS);
+    let parts = [Part::One, Part::Two];
+
+    let answers =
+        |report: Report| -> Vec<_> { report.parts.into_iter().map(|part| part.answer).collect() };
+    let parsed = answers(solve::<S>(&input, &params, &parts).unwrap());
+
+    let json = dump_parsed::<S>(&input, &params).unwrap();
+    let loaded = solve_parsed::<S>(&json, &parts).unwrap_or_else(|err| panic!("{day}: {err}"));
+    assert_eq!(answers(loaded), parsed, "{day}");
+}
+
+/// One test per day, named after its crate.
+macro_rules! round_trips {
+    ($($day:ident: $solution:ty,)*) => {
+        $(
+            #[test]
+            fn $day() {
+                round_trip::<$solution>(stringify!($day));
+            }
+        )*
+    };
+}
+
+round_trips! {
+    day01: day01::Day01,
+    day02: day02::Day02,
+    day03: day03::Day03,
+    day04: day04::Day04,
+    day05: day05::Day05,
+    day06: day06::Day06,
+    day07: day07::Day07,
+    day08: day08::Day08,
+    day09: day09::Day09,
+    day10: day10::Day10,
+    day11: day11::Day11,
+    day12: day12::Day12,
+    day13: day13::Day13,
+    day14: day14::Day14,
+    day15: day15::Day15,
+    day16: day16::Day16,
+    day18: day18::Day18,
+    day19: day19::Day19,
+}
+
+/// A memory space too big to allocate is turned away on loading, instead of
+/// when the solver builds its grid.
+#[test]
+fn day18_size_check() {
+    let json = format!(
+        r#"{{"corrupted_blocks":[],"grid_size":{},"input_len":0}}"#,
+        usize::MAX
+    );
+    let Err(err) = solve_parsed::<day18::Day18>(&json, &[Part::One]) else {
+        panic!("solved a {}x{0} memory space", usize::MAX);
+    };
+    assert!(err.expected().contains("memory space is not between"), "{err}");
+    let json = r#"{"corrupted_blocks":[],"grid_size":0,"input_len":0}"#;
+    assert!(solve_parsed::<day18::Day18>(json, &[Part::One]).is_err());
+}
@@ -0,0 +1,42 @@
+//! `aoc run --render` reads its input only once, so that it can come from
+//! stdin.
+
+use std::{
+    fs,
+    io::Write,
+    path::PathBuf,
+    process::{Command, Stdio},
+};
+
+#[test]
+fn render_stdin_test() {
+    let example = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("day10")
+        .join("input")
+        .join("test_input.txt");
+    let dir = std::env::temp_dir().join(format!("aoc-render-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let picture = dir.join("day10.ppm");
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(["run", "10", "--input", "-", "--render"])
+        .arg(&picture)
+        .env("AOC_ANSWERS_FILE", dir.join("answers.toml"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let input = fs::read(example).unwrap();
+    child.stdin.take().unwrap().write_all(&input).unwrap();
+    let output = child.wait_with_output().unwrap();
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "{stderr}");
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Day 10 part 2: 81"));
+    // a PPM header with the size of the 8x8 example, at 4 pixels per cell
+    assert!(fs::read(&picture).unwrap().starts_with(b"P6\n32 32\n"));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,133 @@
+//! `aoc fetch` and `aoc submit` against the local stand-in for the puzzle site.
+
+mod mock_server;
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Output},
+};
+
+use mock_server::MockServer;
+
+const SESSION: &str = "53616c7465645f5f";
+
+/// Scratch directory holding the session token, the inputs and the cache.
+struct Sandbox {
+    dir: PathBuf,
+}
+
+impl Sandbox {
+    fn new(name: &str) -> Sandbox {
+        let dir = std::env::temp_dir().join(format!("aoc-site-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("inputs")).unwrap();
+        fs::write(dir.join("session"), format!("{SESSION}\n")).unwrap();
+        Sandbox { dir }
+    }
+
+    fn aoc(&self, server: &MockServer, args: &[&str]) -> Output {
+        Command::new(env!("CARGO_BIN_EXE_aoc"))
+            .args(args)
+            .env("AOC_BASE_URL", &server.url)
+            .env("AOC_SESSION_FILE", self.dir.join("session"))
+            .env("AOC_CACHE_DIR", self.dir.join("cache"))
+            .env("AOC_INPUT_DIR", self.dir.join("inputs"))
+            .output()
+            .unwrap()
+    }
+
+    fn path(&self, file: &str) -> PathBuf {
+        self.dir.join(file)
+    }
+}
+
+impl Drop for Sandbox {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+fn read(path: &Path) -> String {
+    fs::read_to_string(path).unwrap()
+}
+
+#[test]
+fn fetch_test() {
+    let server = MockServer::start(SESSION);
+    server.add_day(1, "3   4\n4   3\n", ["11", "31"]);
+    let sandbox = Sandbox::new("fetch");
+
+    let output = sandbox.aoc(&server, &["fetch", "1"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(read(&sandbox.path("inputs/day01.txt")), "3   4\n4   3\n");
+
+    // cached now, so the site is not asked again unless forced
+    let output = sandbox.aoc(&server, &["fetch", "1"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(server.requests(), vec!["GET /2024/day/1/input"]);
+
+    let output = sandbox.aoc(&server, &["fetch", "1", "--force"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(server.requests().len(), 2);
+
+    // locked or unknown days
+    let output = sandbox.aoc(&server, &["fetch", "2"]);
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("not found"));
+    assert!(!sandbox.path("inputs/day02.txt").exists());
+}
+
+#[test]
+fn bad_session_test() {
+    let server = MockServer::start(SESSION);
+    server.add_day(1, "3   4\n", ["11", "31"]);
+    let sandbox = Sandbox::new("session");
+    fs::write(sandbox.path("session"), "expired").unwrap();
+
+    let output = sandbox.aoc(&server, &["fetch", "1"]);
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("session token"));
+    assert!(!sandbox.path("inputs/day01.txt").exists());
+}
+
+#[test]
+fn submit_test() {
+    let server = MockServer::start(SESSION);
+    server.add_day(1, "3   4\n", ["11", "6,1"]);
+    let sandbox = Sandbox::new("submit");
+
+    let output = sandbox.aoc(&server, &["submit", "1", "1", "20"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("too high"));
+    assert_eq!(server.requests().len(), 1);
+
+    // known to be wrong: refused without asking the site
+    for answer in ["20", "25"] {
+        let output = sandbox.aoc(&server, &["submit", "1", "1", answer]);
+        assert!(!output.status.success());
+        assert!(stderr(&output).contains("not submitting"));
+    }
+    assert_eq!(server.requests().len(), 1);
+
+    let output = sandbox.aoc(&server, &["submit", "1", "1", "11"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    let output = sandbox.aoc(&server, &["submit", "1", "1", "11"]);
+    assert!(stderr(&output).contains("already accepted"));
+
+    let output = sandbox.aoc(&server, &["submit", "1", "2", "6,1"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(server.requests().len(), 3);
+
+    let log = read(&sandbox.path("cache/submissions.tsv"));
+    let verdicts: Vec<&str> = log
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .map(|line| line.split('\t').nth(2).unwrap())
+        .collect();
+    assert_eq!(verdicts, vec!["too-high", "correct", "correct"]);
+}
@@ -0,0 +1,377 @@
+use std::io::{self, IsTerminal, Write};
+
+use aoc_common::Simulation;
+use aoc_grid::Pos;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{self, Style},
+    text::{Line, Span},
+    widgets::{Block, Paragraph, Wrap},
+    DefaultTerminal,
+};
+
+use crate::{Cell, Color, Render, Timeline};
+
+/// Steps taken at once by page up and page down.
+const STRIDE: usize = 100;
+
+const PANEL_WIDTH: u16 = 36;
+
+const KEYS: &[&str] = &[
+    "←/→: step back/forward",
+    "pgup/pgdn: 100 steps",
+    "home/end: first/last step",
+    "g: go to step",
+    "w/a/s/d: scroll",
+    "q: quit",
+];
+
+/// A simulation that can be stepped through in the [`Debugger`].
+pub trait Inspect: Simulation + Render + Clone {
+    /// What is worth knowing about the current step besides its picture, as
+    /// names and values.
+    fn details(&self) -> Vec<(&'static str, String)>;
+
+    /// Where things happen, which the debugger keeps in view.
+    fn focus(&self) -> Option<Pos> {
+        None
+    }
+}
+
+/// Steps through a simulation in the terminal, forwards and backwards, with
+/// the grid on the left and the [`Inspect::details`] on the right.
+///
+/// When stdout is not a terminal, the step it starts at is printed once
+/// without colors instead.
+pub struct Debugger<S> {
+    timeline: Timeline<S>,
+    /// The step number typed after `g`.
+    goto: Option<String>,
+    /// Row and column of the top left cell in view.
+    scroll: (usize, usize),
+    /// Whether to bring the focus back into view, after the step changed.
+    follow: bool,
+}
+
+impl<S: Inspect> Debugger<S> {
+    pub fn new(simulation: S) -> Debugger<S> {
+        Debugger {
+            timeline: Timeline::new(simulation),
+            goto: None,
+            scroll: (0, 0),
+            follow: true,
+        }
+    }
+
+    /// Starts at `step`, or at the end if the simulation ends before.
+    pub fn at(mut self, step: usize) -> Debugger<S> {
+        self.timeline.seek(step);
+        self
+    }
+
+    pub fn run(&mut self) -> io::Result<()> {
+        let mut stdout = io::stdout();
+        if !stdout.is_terminal() {
+            return self.print(&mut stdout);
+        }
+
+        let mut terminal = ratatui::try_init()?;
+        let result = self.event_loop(&mut terminal);
+        ratatui::try_restore()?;
+        result
+    }
+
+    fn print(&self, out: &mut impl Write) -> io::Result<()> {
+        writeln!(out, "{}\n", self.timeline.current().render())?;
+        writeln!(out, "{}", self.position())?;
+        for (name, value) in self.timeline.current().details() {
+            writeln!(out, "{name}: {value}")?;
+        }
+        out.flush()
+    }
+
+    fn event_loop(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+            match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press && !self.handle(key) => {
+                    return Ok(());
+                }
+                // a resize only needs a redraw
+                _ => {}
+            }
+        }
+    }
+
+    /// Acts on a key; false to quit.
+    fn handle(&mut self, key: KeyEvent) -> bool {
+        if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            return false;
+        }
+
+        if let Some(typed) = &mut self.goto {
+            match key.code {
+                KeyCode::Char(digit) if digit.is_ascii_digit() => typed.push(digit),
+                KeyCode::Backspace => {
+                    typed.pop();
+                }
+                KeyCode::Enter => {
+                    // too many digits for a step number go as far as they can
+                    let target = typed.parse().unwrap_or(usize::MAX);
+                    self.goto = None;
+                    self.seek(target);
+                }
+                KeyCode::Esc => self.goto = None,
+                _ => {}
+            }
+            return true;
+        }
+
+        let step = self.timeline.step();
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Right | KeyCode::Char('l') | KeyCode::Char('n') => self.seek(step + 1),
+            KeyCode::Left | KeyCode::Char('h') | KeyCode::Char('p') => {
+                self.seek(step.saturating_sub(1))
+            }
+            KeyCode::PageDown => self.seek(step + STRIDE),
+            KeyCode::PageUp => self.seek(step.saturating_sub(STRIDE)),
+            KeyCode::Home => self.seek(0),
+            KeyCode::End => self.seek(usize::MAX),
+            KeyCode::Char('g') => self.goto = Some(String::new()),
+            KeyCode::Char('w') => self.scroll.0 = self.scroll.0.saturating_sub(1),
+            KeyCode::Char('s') => self.scroll.0 += 1,
+            KeyCode::Char('a') => self.scroll.1 = self.scroll.1.saturating_sub(1),
+            KeyCode::Char('d') => self.scroll.1 += 1,
+            _ => {}
+        }
+        true
+    }
+
+    fn seek(&mut self, target: usize) {
+        self.timeline.seek(target);
+        self.follow = true;
+    }
+
+    /// `step N`, followed by the number of the last step once it is known.
+    fn position(&self) -> String {
+        match self.timeline.end() {
+            Some(end) => format!("step {} of {end}", self.timeline.step()),
+            None => format!("step {}", self.timeline.step()),
+        }
+    }
+
+    fn draw(&mut self, frame: &mut ratatui::Frame) {
+        let [grid_area, panel_area] =
+            Layout::horizontal([Constraint::Min(0), Constraint::Length(PANEL_WIDTH)])
+                .areas(frame.area());
+
+        let simulation = self.timeline.current();
+        let picture = simulation.render();
+        let grid_block = Block::bordered();
+        let view = grid_block.inner(grid_area);
+        if self.follow {
+            if let Some(focus) = simulation.focus() {
+                self.scroll = scroll_to(self.scroll, focus, view);
+            }
+            self.follow = false;
+        }
+
+        let cells = picture.cells();
+        self.scroll.0 = self.scroll.0.min(cells.height().saturating_sub(1));
+        self.scroll.1 = self.scroll.1.min(cells.width().saturating_sub(1));
+        let lines: Vec<Line> = cells
+            .rows()
+            .skip(self.scroll.0)
+            .take(view.height.into())
+            .map(|row| line(&row[self.scroll.1..], view.width.into()))
+            .collect();
+        frame.render_widget(Paragraph::new(lines).block(grid_block), grid_area);
+
+        let mut panel = vec![Line::from(self.position()), Line::default()];
+        for (name, value) in simulation.details() {
+            panel.push(Line::from(vec![
+                Span::styled(format!("{name}: "), Style::new().fg(style::Color::DarkGray)),
+                Span::raw(value),
+            ]));
+        }
+        panel.push(Line::default());
+        panel.push(Line::from(picture.caption().to_string()));
+        panel.push(Line::default());
+        match &self.goto {
+            Some(typed) => panel.push(Line::from(format!("go to step: {typed}_"))),
+            None => panel.extend(KEYS.iter().map(|keys| Line::from(*keys))),
+        }
+        let panel = Paragraph::new(panel)
+            .wrap(Wrap { trim: false })
+            .block(Block::bordered().title(" debugger "));
+        frame.render_widget(panel, panel_area);
+    }
+}
+
+/// `scroll` moved just enough to show `focus` in `view`, or centred on it if
+/// it is far out of view.
+fn scroll_to(scroll: (usize, usize), focus: Pos, view: Rect) -> (usize, usize) {
+    let axis = |scroll: usize, focus: i64, size: u16| -> usize {
+        let (Ok(focus), size) = (usize::try_from(focus), usize::from(size)) else {
+            return scroll;
+        };
+        if (scroll..scroll + size).contains(&focus) {
+            scroll
+        } else {
+            focus.saturating_sub(size / 2)
+        }
+    };
+    (
+        axis(scroll.0, focus.y, view.height),
+        axis(scroll.1, focus.x, view.width),
+    )
+}
+
+/// The first `width` cells of `row`, with runs of the same colors in a span.
+fn line(row: &[Cell], width: usize) -> Line<'static> {
+    let mut spans: Vec<Span> = Vec::new();
+    let mut run = String::new();
+    let mut colors = None;
+
+    for cell in row.iter().take(width) {
+        if colors.is_some_and(|colors| colors != (cell.fg, cell.bg)) {
+            spans.push(span(std::mem::take(&mut run), colors.unwrap()));
+        }
+        colors = Some((cell.fg, cell.bg));
+        run.push(cell.chr);
+    }
+    if let Some(colors) = colors {
+        spans.push(span(run, colors));
+    }
+    Line::from(spans)
+}
+
+fn span(text: String, (fg, bg): (Color, Color)) -> Span<'static> {
+    Span::styled(text, Style::new().fg(tui_color(fg)).bg(tui_color(bg)))
+}
+
+fn tui_color(color: Color) -> style::Color {
+    match color {
+        Color::Default => style::Color::Reset,
+        Color::Black => style::Color::Black,
+        Color::Red => style::Color::Red,
+        Color::Green => style::Color::Green,
+        Color::Yellow => style::Color::Yellow,
+        Color::Blue => style::Color::Blue,
+        Color::Magenta => style::Color::Magenta,
+        Color::Cyan => style::Color::Cyan,
+        Color::White => style::Color::White,
+        Color::Gray => style::Color::DarkGray,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_grid::Grid;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    use super::*;
+    use crate::Frame;
+
+    /// A `@` walking right along a row of 100 dots.
+    #[derive(Clone)]
+    struct Walk {
+        x: i64,
+    }
+
+    impl Simulation for Walk {
+        type State = i64;
+
+        fn state(&self) -> i64 {
+            self.x
+        }
+
+        fn step(&mut self) -> bool {
+            if self.x == 99 {
+                return false;
+            }
+            self.x += 1;
+            true
+        }
+    }
+
+    impl Render for Walk {
+        fn render(&self) -> Frame {
+            let mut frame = Frame::from_grid(&Grid::new(100, 1, '.'), |chr| Cell::new(*chr));
+            frame.set(Pos::new(0, self.x), Cell::new('@').fg(Color::Red));
+            frame.with_caption(format!("walked {}", self.x))
+        }
+    }
+
+    impl Inspect for Walk {
+        fn details(&self) -> Vec<(&'static str, String)> {
+            vec![("x", self.x.to_string())]
+        }
+
+        fn focus(&self) -> Option<Pos> {
+            Some(Pos::new(0, self.x))
+        }
+    }
+
+    fn screen(debugger: &mut Debugger<Walk>) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(60, 12)).unwrap();
+        terminal.draw(|frame| debugger.draw(frame)).unwrap();
+        let buffer = terminal.backend().buffer();
+        (0..buffer.area.height)
+            .map(|y| {
+                (0..buffer.area.width)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn press(debugger: &mut Debugger<Walk>, keys: &[KeyCode]) {
+        for key in keys {
+            assert!(debugger.handle(KeyEvent::from(*key)));
+        }
+    }
+
+    #[test]
+    fn debugger_test() {
+        let mut debugger = Debugger::new(Walk { x: 0 }).at(5);
+        let screen_5 = screen(&mut debugger);
+        assert!(screen_5.contains("│.....@....."));
+        assert!(screen_5.contains("step 5 "));
+        assert!(screen_5.contains("x: 5 "));
+        assert!(screen_5.contains("walked 5"));
+
+        press(
+            &mut debugger,
+            &[KeyCode::Right, KeyCode::Right, KeyCode::Left],
+        );
+        assert!(screen(&mut debugger).contains("x: 6 "));
+
+        press(&mut debugger, &[KeyCode::End]);
+        let screen_end = screen(&mut debugger);
+        assert!(screen_end.contains("step 99 of 99"));
+        // scrolled along to keep the `@` in view
+        assert!(screen_end.contains(".@"));
+
+        press(&mut debugger, &[KeyCode::Char('g'), KeyCode::Char('4')]);
+        assert!(screen(&mut debugger).contains("go to step: 4_"));
+        press(&mut debugger, &[KeyCode::Char('2'), KeyCode::Enter]);
+        assert!(screen(&mut debugger).contains("x: 42 "));
+
+        press(&mut debugger, &[KeyCode::Home, KeyCode::PageDown]);
+        assert!(screen(&mut debugger).contains("x: 99 "));
+        assert!(!debugger.handle(KeyEvent::from(KeyCode::Char('q'))));
+    }
+
+    #[test]
+    fn print_test() {
+        let mut out = Vec::new();
+        Debugger::new(Walk { x: 0 }).at(3).print(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.starts_with("...@..."));
+        assert!(out.ends_with("walked 3\n\nstep 3\nx: 3\n"));
+    }
+}
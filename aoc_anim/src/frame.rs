@@ -0,0 +1,117 @@
+use std::fmt;
+
+use aoc_grid::{Grid, Pos};
+
+/// The terminal's basic palette; `Default` keeps whatever the terminal uses.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub enum Color {
+    #[default]
+    Default,
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    Gray,
+}
+
+/// One character cell of a frame.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Cell {
+    pub chr: char,
+    pub fg: Color,
+    pub bg: Color,
+}
+
+impl Cell {
+    pub const fn new(chr: char) -> Cell {
+        Cell {
+            chr,
+            fg: Color::Default,
+            bg: Color::Default,
+        }
+    }
+
+    pub const fn fg(self, fg: Color) -> Cell {
+        Cell { fg, ..self }
+    }
+
+    pub const fn bg(self, bg: Color) -> Cell {
+        Cell { bg, ..self }
+    }
+}
+
+impl From<char> for Cell {
+    fn from(chr: char) -> Cell {
+        Cell::new(chr)
+    }
+}
+
+impl fmt::Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.chr)
+    }
+}
+
+/// A picture of a simulation at one point in time, with a line of text
+/// telling what is going on.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Frame {
+    cells: Grid<Cell>,
+    caption: String,
+}
+
+impl Frame {
+    pub fn new(cells: Grid<Cell>) -> Frame {
+        Frame {
+            cells,
+            caption: String::new(),
+        }
+    }
+
+    /// Draws every cell of `grid` with `f`.
+    pub fn from_grid<T>(grid: &Grid<T>, f: impl FnMut(&T) -> Cell) -> Frame {
+        Frame::new(grid.map(f))
+    }
+
+    pub fn with_caption(self, caption: impl Into<String>) -> Frame {
+        Frame {
+            caption: caption.into(),
+            ..self
+        }
+    }
+
+    /// Overwrites the cell at `pos`; positions outside of the frame are ignored.
+    pub fn set(&mut self, pos: Pos, cell: impl Into<Cell>) {
+        if let Some(target) = pos.to_idx().and_then(|idx| self.cells.get_mut(idx)) {
+            *target = cell.into();
+        }
+    }
+
+    pub fn cells(&self) -> &Grid<Cell> {
+        &self.cells
+    }
+
+    pub fn caption(&self) -> &str {
+        &self.caption
+    }
+}
+
+/// Without colors, followed by the caption if there is one.
+impl fmt::Display for Frame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.cells)?;
+        if !self.caption.is_empty() {
+            write!(f, "\n{}", self.caption)?;
+        }
+        Ok(())
+    }
+}
+
+/// Something that can be drawn as a single frame.
+pub trait Render {
+    fn render(&self) -> Frame;
+}
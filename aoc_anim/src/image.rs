@@ -0,0 +1,195 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, ErrorKind, Write},
+    path::Path,
+};
+
+use crate::{Cell, Color, Frame};
+
+/// Red, green and blue.
+pub type Rgb = [u8; 3];
+
+/// The actual colors behind [`Color`] when a frame is drawn as an image.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Palette {
+    /// Behind everything, and where a cell shows nothing.
+    pub background: Rgb,
+    /// For characters in [`Color::Default`].
+    pub foreground: Rgb,
+    colors: [Rgb; 9],
+}
+
+/// Like a dark terminal.
+impl Default for Palette {
+    fn default() -> Palette {
+        Palette {
+            background: [24, 24, 32],
+            foreground: [220, 220, 220],
+            colors: [
+                [0, 0, 0],
+                [220, 50, 47],
+                [80, 200, 80],
+                [230, 200, 60],
+                [60, 110, 230],
+                [200, 80, 200],
+                [60, 200, 210],
+                [250, 250, 250],
+                [64, 64, 76],
+            ],
+        }
+    }
+}
+
+impl Palette {
+    pub fn with(mut self, color: Color, rgb: Rgb) -> Palette {
+        match color {
+            Color::Default => self.foreground = rgb,
+            color => self.colors[color as usize - 1] = rgb,
+        }
+        self
+    }
+
+    pub fn rgb(&self, color: Color) -> Rgb {
+        match color {
+            Color::Default => self.foreground,
+            color => self.colors[color as usize - 1],
+        }
+    }
+
+    /// A cell as a single color: its background if it has one, otherwise its
+    /// character's, unless that is blank or a `.`.
+    pub fn cell(&self, cell: &Cell) -> Rgb {
+        match cell {
+            Cell { bg, .. } if *bg != Color::Default => self.rgb(*bg),
+            Cell { chr: ' ' | '.', .. } => self.background,
+            Cell { fg, .. } => self.rgb(*fg),
+        }
+    }
+}
+
+/// A picture in 8-bit RGB.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    /// Draws every cell of `frame` as a `scale` by `scale` square, without
+    /// the caption.
+    pub fn from_frame(frame: &Frame, palette: &Palette, scale: usize) -> Image {
+        let cells = frame.cells();
+        let (width, height) = (cells.width() * scale, cells.height() * scale);
+
+        let mut pixels = Vec::with_capacity(width * height);
+        for y in 0..height {
+            pixels.extend((0..width).map(|x| palette.cell(&cells[(y / scale, x / scale)])));
+        }
+
+        Image {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The pixel `x` from the left and `y` from the top.
+    pub fn pixel(&self, x: usize, y: usize) -> Rgb {
+        self.pixels[y * self.width + x]
+    }
+
+    /// As a binary PPM, which almost anything can open and convert.
+    pub fn write_ppm(&self, mut out: impl Write) -> io::Result<()> {
+        write!(out, "P6\n{} {}\n255\n", self.width, self.height)?;
+        out.write_all(self.pixels.as_flattened())?;
+        out.flush()
+    }
+
+    pub fn write_png(&self, out: impl Write) -> io::Result<()> {
+        let too_large = || io::Error::new(ErrorKind::InvalidInput, "too large for a PNG");
+        let width = u32::try_from(self.width).map_err(|_| too_large())?;
+        let height = u32::try_from(self.height).map_err(|_| too_large())?;
+
+        let mut encoder = png::Encoder::new(out, width, height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(self.pixels.as_flattened())?;
+        Ok(writer.finish()?)
+    }
+
+    /// Writes a PNG or a PPM, as the extension of `path` says.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let extension = path.extension().and_then(|extension| extension.to_str());
+        let write = match extension.map(str::to_ascii_lowercase).as_deref() {
+            Some("png") => Image::write_png,
+            Some("ppm") => Image::write_ppm,
+            _ => {
+                return Err(io::Error::new(
+                    ErrorKind::InvalidInput,
+                    "expected a file name ending in `.png` or `.ppm`",
+                ))
+            }
+        };
+        write(self, BufWriter::new(File::create(path)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_grid::{Grid, Pos};
+
+    use super::*;
+
+    fn frame() -> Frame {
+        let grid = Grid::parse("#.\n.x", |chr| chr).unwrap();
+        let mut frame = Frame::from_grid(&grid, |chr| match chr {
+            '#' => Cell::new('#').fg(Color::Gray),
+            chr => Cell::new(*chr),
+        });
+        frame.set(Pos::new(1, 0), Cell::new('.').bg(Color::Blue));
+        frame
+    }
+
+    #[test]
+    fn from_frame_test() {
+        let palette = Palette::default().with(Color::Gray, [1, 2, 3]);
+        let image = Image::from_frame(&frame(), &palette, 3);
+        assert_eq!((image.width(), image.height()), (6, 6));
+
+        assert_eq!(image.pixel(0, 0), [1, 2, 3]);
+        assert_eq!(image.pixel(2, 2), [1, 2, 3]);
+        assert_eq!(image.pixel(3, 0), palette.background);
+        assert_eq!(image.pixel(0, 3), palette.rgb(Color::Blue));
+        assert_eq!(image.pixel(5, 5), palette.foreground);
+    }
+
+    #[test]
+    fn write_test() {
+        let image = Image::from_frame(&frame(), &Palette::default(), 2);
+
+        let mut ppm = Vec::new();
+        image.write_ppm(&mut ppm).unwrap();
+        assert!(ppm.starts_with(b"P6\n4 4\n255\n"));
+        assert_eq!(ppm.len(), 11 + 4 * 4 * 3);
+
+        let mut png = Vec::new();
+        image.write_png(&mut png).unwrap();
+        let mut reader = png::Decoder::new(png.as_slice()).read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        reader.next_frame(&mut pixels).unwrap();
+        assert_eq!(pixels, ppm[11..]);
+
+        let err = image.save(Path::new("picture.gif")).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+}
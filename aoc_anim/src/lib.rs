@@ -0,0 +1,74 @@
+//! Frame by frame pictures of the simulation days, a terminal player for
+//! them, a debugger to step through them and images of single frames.
+
+mod debugger;
+mod frame;
+mod image;
+mod player;
+mod timeline;
+
+use std::{io, path::Path};
+
+use aoc_common::Solution;
+
+pub use debugger::{Debugger, Inspect};
+pub use frame::{Cell, Color, Frame, Render};
+pub use image::{Image, Palette, Rgb};
+pub use player::Player;
+pub use timeline::Timeline;
+
+/// A solution whose simulation can be watched step by step.
+pub trait Animate: Solution {
+    /// Frames of the simulation on `input`, from the start to the end.
+    fn frames(input: &Self::Input) -> impl Iterator<Item = Frame> + '_;
+}
+
+/// A solution that can draw its input in a single frame, with what it found
+/// in there drawn over it.
+pub trait Picture: Solution {
+    fn picture(input: &Self::Input) -> Frame;
+}
+
+/// A solution whose simulation can be stepped through forwards and backwards
+/// in the [`Debugger`].
+pub trait StepThrough: Solution {
+    /// The simulation on `input`, at its start.
+    fn simulation(input: &Self::Input) -> impl Inspect + '_;
+}
+
+/// Draws the picture of `input` to `path`, in the default palette and with
+/// `scale` pixels per cell. See [`Image::save`] for the formats.
+pub fn save_picture<S: Picture>(input: &S::Input, path: &Path, scale: usize) -> io::Result<()> {
+    Image::from_frame(&S::picture(input), &Palette::default(), scale).save(path)
+}
+
+/// Opens the debugger on the simulation of `input`, at `step`.
+pub fn debug<S: StepThrough>(input: &S::Input, step: usize) -> io::Result<()> {
+    Debugger::new(S::simulation(input)).at(step).run()
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_grid::{Grid, Pos};
+
+    use super::*;
+
+    #[test]
+    fn frame_test() {
+        let grid = Grid::parse("#..\n.#.", |chr| chr).unwrap();
+        let mut frame = Frame::from_grid(&grid, |chr| match chr {
+            '#' => Cell::new('#').fg(Color::Gray),
+            chr => Cell::new(*chr),
+        });
+        frame.set(Pos::new(1, 2), Cell::new('@').fg(Color::Red));
+        frame.set(Pos::new(2, 0), '@');
+        frame.set(Pos::new(0, -1), '@');
+
+        assert_eq!(frame.to_string(), "#..\n.#@");
+        assert_eq!(frame.cells()[(0, 0)].fg, Color::Gray);
+        assert_eq!(frame.cells()[(1, 2)].fg, Color::Red);
+
+        let frame = frame.with_caption("step 1");
+        assert_eq!(frame.to_string(), "#..\n.#@\nstep 1");
+    }
+}
@@ -0,0 +1,247 @@
+use std::{
+    io::{self, IsTerminal, Write},
+    time::{Duration, Instant},
+};
+
+use crossterm::{
+    cursor::{Hide, MoveTo, MoveToNextLine, Show},
+    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    execute, queue,
+    style::{self, Print, ResetColor, SetBackgroundColor, SetForegroundColor},
+    terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
+};
+
+use crate::{Color, Frame};
+
+const MIN_FPS: f64 = 0.25;
+const MAX_FPS: f64 = 1000.0;
+
+/// How long to wait for a key while nothing moves on its own.
+const IDLE_POLL: Duration = Duration::from_secs(3600);
+
+const KEYS: &str = "space: play/pause  →: step  +/-: speed  q: quit";
+
+/// Plays a stream of frames in the terminal.
+///
+/// Space pauses and resumes, the right arrow (or `n`) shows the next frame,
+/// `+` and `-` double and halve the speed and `q` quits. When stdout is not
+/// a terminal, every frame is printed once without colors instead.
+pub struct Player {
+    fps: f64,
+    paused: bool,
+}
+
+impl Default for Player {
+    fn default() -> Player {
+        Player::new()
+    }
+}
+
+impl Player {
+    pub fn new() -> Player {
+        Player {
+            fps: 10.0,
+            paused: false,
+        }
+    }
+
+    /// Frames shown per second while playing, kept to a sensible range. A
+    /// NaN leaves the speed as it was.
+    pub fn fps(self, fps: f64) -> Player {
+        if fps.is_nan() {
+            return self;
+        }
+        Player {
+            fps: fps.clamp(MIN_FPS, MAX_FPS),
+            ..self
+        }
+    }
+
+    /// Whether to wait on the first frame until told to play.
+    pub fn paused(self, paused: bool) -> Player {
+        Player { paused, ..self }
+    }
+
+    pub fn play(&mut self, frames: impl IntoIterator<Item = Frame>) -> io::Result<()> {
+        let mut stdout = io::stdout();
+        if !stdout.is_terminal() {
+            for frame in frames {
+                match writeln!(stdout, "{frame}\n") {
+                    // whoever reads the frames has seen enough
+                    Err(err) if err.kind() == io::ErrorKind::BrokenPipe => break,
+                    result => result?,
+                }
+            }
+            return Ok(());
+        }
+
+        let _screen = Screen::enter()?;
+        self.run(frames.into_iter(), &mut stdout)
+    }
+
+    fn interval(&self) -> Duration {
+        Duration::from_secs_f64(1.0 / self.fps)
+    }
+
+    fn run(
+        &mut self,
+        mut frames: impl Iterator<Item = Frame>,
+        out: &mut impl Write,
+    ) -> io::Result<()> {
+        let Some(mut frame) = frames.next() else {
+            return Ok(());
+        };
+        let mut number = 1;
+        let mut ended = false;
+        let mut next_at = Instant::now() + self.interval();
+
+        loop {
+            let state = match (ended, self.paused) {
+                (true, _) => "end",
+                (false, true) => "paused",
+                (false, false) => "playing",
+            };
+            let status = format!("frame {number} | {} fps | {state} | {KEYS}", self.fps);
+            draw(out, &frame, &status)?;
+
+            let timeout = if ended || self.paused {
+                IDLE_POLL
+            } else {
+                next_at.saturating_duration_since(Instant::now())
+            };
+
+            let mut advance = false;
+            if event::poll(timeout)? {
+                let Event::Key(key) = event::read()? else {
+                    // a resize only needs a redraw
+                    continue;
+                };
+                if key.kind != KeyEventKind::Press {
+                    continue;
+                }
+                match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                    KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        return Ok(());
+                    }
+                    KeyCode::Char(' ') => {
+                        self.paused = !self.paused;
+                        next_at = Instant::now() + self.interval();
+                    }
+                    KeyCode::Right | KeyCode::Char('n') | KeyCode::Char('.') => {
+                        self.paused = true;
+                        advance = true;
+                    }
+                    KeyCode::Char('+') | KeyCode::Char('=') => {
+                        self.fps = (self.fps * 2.0).min(MAX_FPS);
+                    }
+                    KeyCode::Char('-') => self.fps = (self.fps / 2.0).max(MIN_FPS),
+                    _ => {}
+                }
+            } else {
+                advance = true;
+                next_at = Instant::now() + self.interval();
+            }
+
+            if advance && !ended {
+                match frames.next() {
+                    Some(next) => {
+                        frame = next;
+                        number += 1;
+                    }
+                    None => ended = true,
+                }
+            }
+        }
+    }
+}
+
+/// Raw mode on the alternate screen for as long as it lives.
+struct Screen;
+
+impl Screen {
+    fn enter() -> io::Result<Screen> {
+        // created first, so that a failure half way still restores the terminal
+        let screen = Screen;
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), EnterAlternateScreen, Hide)?;
+        Ok(screen)
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), Show, LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Draws `frame` from the top left corner, cut to the size of the terminal,
+/// with its caption and `status` below.
+fn draw(out: &mut impl Write, frame: &Frame, status: &str) -> io::Result<()> {
+    let (columns, rows) = terminal::size()?;
+    let (columns, rows) = (columns as usize, rows as usize);
+    let cells = frame.cells();
+
+    queue!(out, MoveTo(0, 0))?;
+    for row in cells.rows().take(rows.saturating_sub(2)) {
+        let mut colors = None;
+        for cell in row.iter().take(columns) {
+            if colors != Some((cell.fg, cell.bg)) {
+                queue!(
+                    out,
+                    SetForegroundColor(terminal_color(cell.fg)),
+                    SetBackgroundColor(terminal_color(cell.bg))
+                )?;
+                colors = Some((cell.fg, cell.bg));
+            }
+            queue!(out, Print(cell.chr))?;
+        }
+        queue!(
+            out,
+            ResetColor,
+            Clear(ClearType::UntilNewLine),
+            MoveToNextLine(1)
+        )?;
+    }
+
+    let cut = |line: &str| line.chars().take(columns).collect::<String>();
+    queue!(
+        out,
+        Print(cut(frame.caption())),
+        Clear(ClearType::UntilNewLine),
+        MoveToNextLine(1),
+        Clear(ClearType::FromCursorDown),
+        Print(cut(status))
+    )?;
+    out.flush()
+}
+
+fn terminal_color(color: Color) -> style::Color {
+    match color {
+        Color::Default => style::Color::Reset,
+        Color::Black => style::Color::Black,
+        Color::Red => style::Color::Red,
+        Color::Green => style::Color::Green,
+        Color::Yellow => style::Color::Yellow,
+        Color::Blue => style::Color::Blue,
+        Color::Magenta => style::Color::Magenta,
+        Color::Cyan => style::Color::Cyan,
+        Color::White => style::Color::White,
+        Color::Gray => style::Color::DarkGrey,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fps_test() {
+        assert_eq!(Player::new().fps(20.0).interval(), Duration::from_millis(50));
+        assert_eq!(Player::new().fps(f64::NAN).interval(), Duration::from_millis(100));
+        assert_eq!(Player::new().fps(f64::INFINITY).interval(), Duration::from_millis(1));
+        assert_eq!(Player::new().fps(f64::NEG_INFINITY).interval(), Duration::from_secs(4));
+        assert_eq!(Player::new().fps(0.0).interval(), Duration::from_secs(4));
+    }
+}
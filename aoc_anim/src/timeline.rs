@@ -0,0 +1,144 @@
+use aoc_common::Simulation;
+
+/// Steps between two copies of the simulation kept by default.
+const CHECKPOINT_INTERVAL: usize = 100;
+
+/// A simulation that can also go back in time.
+///
+/// Simulations only step forward, so a copy of it is kept every so many
+/// steps. Going back restores the last copy before the target and steps on
+/// from there, which never takes more than the interval in steps.
+pub struct Timeline<S> {
+    current: S,
+    step: usize,
+    /// The simulation at step `idx * interval`, for every such step reached.
+    checkpoints: Vec<S>,
+    interval: usize,
+    /// The last step, once the simulation got there.
+    end: Option<usize>,
+}
+
+impl<S: Simulation + Clone> Timeline<S> {
+    pub fn new(start: S) -> Timeline<S> {
+        Timeline::with_interval(start, CHECKPOINT_INTERVAL)
+    }
+
+    /// Keeps a copy of the simulation every `interval` steps.
+    pub fn with_interval(start: S, interval: usize) -> Timeline<S> {
+        Timeline {
+            checkpoints: vec![start.clone()],
+            current: start,
+            step: 0,
+            interval: interval.max(1),
+            end: None,
+        }
+    }
+
+    pub fn current(&self) -> &S {
+        &self.current
+    }
+
+    /// Number of steps taken from the start.
+    pub fn step(&self) -> usize {
+        self.step
+    }
+
+    /// The number of the last step, once it has been reached.
+    pub fn end(&self) -> Option<usize> {
+        self.end
+    }
+
+    /// One step forward; false at the end.
+    pub fn forward(&mut self) -> bool {
+        if self.end == Some(self.step) || !self.current.step() {
+            self.end = Some(self.step);
+            return false;
+        }
+
+        self.step += 1;
+        if self.step == self.checkpoints.len() * self.interval {
+            self.checkpoints.push(self.current.clone());
+        }
+        true
+    }
+
+    /// One step back; false at the start.
+    pub fn back(&mut self) -> bool {
+        match self.step {
+            0 => false,
+            step => {
+                self.seek(step - 1);
+                true
+            }
+        }
+    }
+
+    /// Goes to step `target`, or to the end if the simulation ends before.
+    /// Returns the step it got to.
+    pub fn seek(&mut self, target: usize) -> usize {
+        let checkpoint = (target / self.interval).min(self.checkpoints.len() - 1);
+        let checkpoint_step = checkpoint * self.interval;
+        // stepping on from here is shorter unless the target lies behind
+        if target < self.step || checkpoint_step > self.step {
+            self.current = self.checkpoints[checkpoint].clone();
+            self.step = checkpoint_step;
+        }
+
+        while self.step < target && self.forward() {}
+        self.step
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Counts up to `end`.
+    #[derive(Clone)]
+    struct Counter {
+        count: usize,
+        end: usize,
+    }
+
+    impl Simulation for Counter {
+        type State = usize;
+
+        fn state(&self) -> usize {
+            self.count
+        }
+
+        fn step(&mut self) -> bool {
+            if self.count == self.end {
+                return false;
+            }
+            self.count += 1;
+            true
+        }
+    }
+
+    #[test]
+    fn timeline_test() {
+        let mut timeline = Timeline::with_interval(Counter { count: 0, end: 25 }, 10);
+        assert!(!timeline.back());
+
+        assert!(timeline.forward());
+        assert_eq!((timeline.step(), timeline.current().count), (1, 1));
+        assert_eq!(timeline.seek(17), 17);
+        assert_eq!(timeline.checkpoints.len(), 2);
+        assert!(timeline.back());
+        assert_eq!((timeline.step(), timeline.current().count), (16, 16));
+        assert_eq!(timeline.seek(3), 3);
+        assert_eq!(timeline.current().count, 3);
+
+        assert_eq!(timeline.end(), None);
+        assert_eq!(timeline.seek(usize::MAX), 25);
+        assert_eq!(timeline.end(), Some(25));
+        assert!(!timeline.forward());
+        assert_eq!(timeline.checkpoints.len(), 3);
+
+        assert_eq!(timeline.seek(20), 20);
+        assert_eq!(timeline.current().count, 20);
+        assert_eq!(timeline.seek(0), 0);
+        assert_eq!(timeline.current().count, 0);
+    }
+}
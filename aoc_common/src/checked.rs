@@ -0,0 +1,51 @@
+//! Integer overflow policy: where the puzzle input can push a value past its
+//! type, solvers use checked arithmetic and fail with
+//! [`SolveError::Overflow`], instead of panicking in debug builds and
+//! silently wrapping in release ones.
+
+use crate::SolveError;
+
+/// Integers with checked addition, for [`checked_sum`].
+pub trait CheckedAdd: Copy {
+    const ZERO: Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_checked_add {
+    ($($int:ty),*) => {
+        $(
+            impl CheckedAdd for $int {
+                const ZERO: $int = 0;
+
+                fn checked_add(self, other: $int) -> Option<$int> {
+                    <$int>::checked_add(self, other)
+                }
+            }
+        )*
+    };
+}
+
+impl_checked_add!(u32, u64, u128, usize, i32, i64, i128);
+
+/// The sum of `values`, unless it does not fit.
+pub fn checked_sum<T: CheckedAdd>(values: impl IntoIterator<Item = T>) -> Result<T, SolveError> {
+    values
+        .into_iter()
+        .try_fold(T::ZERO, T::checked_add)
+        .ok_or(SolveError::Overflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checked_sum_test() {
+        assert_eq!(checked_sum([1u64, 2, 3]), Ok(6));
+        assert_eq!(checked_sum(Vec::<usize>::new()), Ok(0));
+        assert_eq!(checked_sum([u64::MAX - 1, 1]), Ok(u64::MAX));
+        assert_eq!(checked_sum([u64::MAX, 1]), Err(SolveError::Overflow));
+        assert_eq!(checked_sum([i64::MIN, -1]), Err(SolveError::Overflow));
+    }
+}
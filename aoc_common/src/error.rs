@@ -0,0 +1,180 @@
+use std::{error::Error, fmt, str::FromStr};
+
+/// Malformed puzzle input, located by 1-based line and column (in characters).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    line: usize,
+    column: usize,
+    expected: String,
+}
+
+impl ParseError {
+    /// Positions are 1-based, so a 0 line or column counts as the first.
+    pub fn new(line: usize, column: usize, expected: impl Into<String>) -> ParseError {
+        ParseError {
+            line: line.max(1),
+            column: column.max(1),
+            expected: expected.into(),
+        }
+    }
+
+    /// Error pointing at the start of `token`, which must be a subslice of `source`.
+    pub fn at(source: &str, token: &str, expected: impl Into<String>) -> ParseError {
+        let offset = (token.as_ptr() as usize)
+            .saturating_sub(source.as_ptr() as usize)
+            .min(source.len());
+        let before = &source.as_bytes()[..offset];
+
+        let line_start = before
+            .iter()
+            .rposition(|byte| *byte == b'\n')
+            .map_or(0, |idx| idx + 1);
+        let line = before[..line_start]
+            .iter()
+            .filter(|byte| **byte == b'\n')
+            .count()
+            + 1;
+        // count characters, not bytes: skip UTF-8 continuation bytes
+        let column = before[line_start..]
+            .iter()
+            .filter(|byte| (**byte & 0xC0) != 0x80)
+            .count()
+            + 1;
+
+        ParseError::new(line, column, expected)
+    }
+
+    /// Error pointing just past the end of `source`, for input that stops too early.
+    pub fn at_end(source: &str, expected: impl Into<String>) -> ParseError {
+        ParseError::at(source, &source[source.len()..], expected)
+    }
+
+    /// Moves an error located inside `section` so that it is relative to `source`,
+    /// which must contain `section`.
+    pub fn within(self, source: &str, section: &str) -> ParseError {
+        let start = ParseError::at(source, section, "");
+        let column = if self.line == 1 {
+            start.column + self.column - 1
+        } else {
+            self.column
+        };
+        ParseError::new(start.line + self.line - 1, column, self.expected)
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    pub fn expected(&self) -> &str {
+        &self.expected
+    }
+
+    /// The offending line of `input` with a caret under the error column.
+    pub fn snippet(&self, input: &str) -> String {
+        let text = input
+            .lines()
+            .nth(self.line - 1)
+            .unwrap_or("")
+            .trim_end_matches('\r');
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+        let caret_pad = " ".repeat(self.column - 1);
+
+        format!(
+            "{gutter} |\n{number} | {text}\n{gutter} | {caret_pad}^ expected {}",
+            self.expected
+        )
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "parse error at line {}, column {}: expected {}",
+            self.line, self.column, self.expected
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses `token` as a number, failing with its position in `source`.
+pub fn parse_number<T: FromStr>(source: &str, token: &str) -> Result<T, ParseError> {
+    token
+        .parse()
+        .map_err(|_| ParseError::at(source, token, "a number"))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    /// The part has not been solved for this day yet.
+    NotImplemented,
+    /// The input admits no answer, e.g. no path through a maze.
+    NoSolution,
+    /// A value does not fit in the integer type the solver keeps it in.
+    Overflow,
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::NotImplemented => write!(f, "not solved"),
+            SolveError::NoSolution => write!(f, "no solution"),
+            SolveError::Overflow => write!(f, "arithmetic overflow"),
+        }
+    }
+}
+
+impl Error for SolveError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locate_test() {
+        let input = "1 2\n3 x\n";
+        let token = &input[6..7];
+        let err = ParseError::at(input, token, "a number");
+        assert_eq!((err.line(), err.column()), (2, 3));
+
+        let err = ParseError::at_end(input, "more lines");
+        assert_eq!((err.line(), err.column()), (3, 1));
+
+        let section = &input[4..];
+        let err = parse_number::<i64>(section, &section[2..3]).unwrap_err();
+        assert_eq!((err.line(), err.column()), (1, 3));
+        let err = err.within(input, section);
+        assert_eq!((err.line(), err.column()), (2, 3));
+    }
+
+    #[test]
+    fn zero_position_test() {
+        let err = ParseError::new(0, 0, "a number");
+        assert_eq!((err.line(), err.column()), (1, 1));
+        assert_eq!(err.snippet("x\n"), "  |\n1 | x\n  | ^ expected a number");
+
+        let input = "1 2\n3 x\n";
+        let err = err.within(input, &input[4..]);
+        assert_eq!((err.line(), err.column()), (2, 1));
+    }
+
+    #[test]
+    fn snippet_test() {
+        let input = "p=0,4\r\np=1,?\r\n";
+        let err = ParseError::at(input, &input[11..12], "a number");
+        assert_eq!(
+            err.to_string(),
+            "parse error at line 2, column 5: expected a number"
+        );
+        assert_eq!(
+            err.snippet(input),
+            "  |\n2 | p=1,?\n  |     ^ expected a number"
+        );
+    }
+}
@@ -0,0 +1,69 @@
+use std::{
+    convert::Infallible,
+    env, fmt, fs,
+    io::{self, Read},
+    path::PathBuf,
+    str::FromStr,
+};
+
+/// Environment variable naming a directory with one `dayNN.txt` input per day.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Where to read a puzzle input from: a file, or stdin when given as `-`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InputSource {
+    Stdin,
+    File(PathBuf),
+}
+
+impl InputSource {
+    /// `explicit` if given, else `dayNN.txt` in `$AOC_INPUT_DIR` if that is
+    /// set, else `fallback`.
+    pub fn select(
+        explicit: Option<InputSource>,
+        day: u8,
+        fallback: impl FnOnce() -> PathBuf,
+    ) -> InputSource {
+        if let Some(source) = explicit {
+            return source;
+        }
+
+        match env::var_os(INPUT_DIR_VAR) {
+            Some(dir) if !dir.is_empty() => {
+                InputSource::File(PathBuf::from(dir).join(format!("day{day:02}.txt")))
+            }
+            _ => InputSource::File(fallback()),
+        }
+    }
+
+    pub fn read(&self) -> io::Result<String> {
+        match self {
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+            InputSource::File(path) => fs::read_to_string(path),
+        }
+    }
+}
+
+impl FromStr for InputSource {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "-" => Ok(InputSource::Stdin),
+            _ => Ok(InputSource::File(PathBuf::from(s))),
+        }
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::Stdin => write!(f, "<stdin>"),
+            InputSource::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
@@ -0,0 +1,164 @@
+mod checked;
+mod error;
+mod input;
+mod logging;
+mod memo;
+mod output;
+mod parallel;
+mod params;
+#[cfg(feature = "serde")]
+mod parsed;
+mod simulation;
+mod solution;
+
+use std::{
+    io,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use clap::{value_parser, Arg, ArgAction, Command};
+
+pub use checked::{checked_sum, CheckedAdd};
+pub use error::{parse_number, ParseError, SolveError};
+pub use input::{InputSource, INPUT_DIR_VAR};
+pub use logging::init_logging;
+pub use memo::{Memo, MemoStats};
+pub use output::{input_hash, json_report, Format};
+pub use parallel::{par_map, par_map_with, set_jobs};
+pub use params::{Param, Params};
+#[cfg(feature = "serde")]
+pub use parsed::{dump_parsed, load_parsed, solve_parsed};
+pub use simulation::{Cycle, Simulation};
+pub use solution::{format_duration, solve, Part, PartReport, Report, Solution};
+
+/// Draws a parsed input to an image file, with the given number of pixels
+/// per grid cell.
+pub type Renderer<S> = fn(&<S as Solution>::Input, &Path, usize) -> io::Result<()>;
+
+/// Entry point shared by the per-day binaries: solves both parts of the input
+/// chosen on the command line, `input/input.txt` by default.
+pub fn run_main<S: Solution>(day: u8) -> ExitCode {
+    main_with::<S>(day, None)
+}
+
+/// Like [`run_main`], with a `--render FILE` option that also draws the
+/// input with `render`.
+pub fn run_main_rendered<S: Solution>(day: u8, render: Renderer<S>) -> ExitCode {
+    main_with::<S>(day, Some(render))
+}
+
+fn main_with<S: Solution>(day: u8, render: Option<Renderer<S>>) -> ExitCode {
+    let mut command = Command::new(format!("day{day:02}"))
+        .about(format!("Advent of Code 2024, day {day}"))
+        .arg(
+            Arg::new("input")
+                .short('i')
+                .long("input")
+                .value_name("FILE")
+                .value_parser(value_parser!(InputSource))
+                .help(format!(
+                    "Puzzle input, `-` for stdin [default: ${INPUT_DIR_VAR}/day{day:02}.txt or input/input.txt]"
+                )),
+        )
+        .arg(
+            Arg::new("format")
+                .long("format")
+                .value_name("FORMAT")
+                .value_parser(value_parser!(Format))
+                .default_value("text")
+                .help("`text`, or `json` for a single machine-readable line"),
+        )
+        .arg(
+            Arg::new("verbose")
+                .short('v')
+                .long("verbose")
+                .action(ArgAction::Count)
+                .help("Show what the solver is doing on stderr, more with -vv and -vvv"),
+        );
+    if render.is_some() {
+        command = command
+            .arg(
+                Arg::new("render")
+                    .long("render")
+                    .value_name("FILE")
+                    .value_parser(value_parser!(PathBuf))
+                    .help("Also draw the input and what was found in it, to a `.png` or `.ppm`"),
+            )
+            .arg(
+                Arg::new("scale")
+                    .long("scale")
+                    .value_name("N")
+                    .value_parser(value_parser!(u16).range(1..))
+                    .default_value("4")
+                    .requires("render")
+                    .help("Pixels per grid cell for --render"),
+            );
+    }
+    for param in S::PARAMS {
+        command = command.arg(
+            Arg::new(param.name)
+                .long(param.name)
+                .value_name("N")
+                .value_parser(value_parser!(u64).range(1..=param.max))
+                .help(format!("{} [default: {}]", param.help, param.default)),
+        );
+    }
+    let matches = command.get_matches();
+    init_logging(matches.get_count("verbose"));
+
+    let mut params = Params::new(S::PARAMS);
+    for param in S::PARAMS {
+        if let Some(value) = matches.get_one::<u64>(param.name) {
+            if let Err(err) = params.set(param.name, *value) {
+                eprintln!("{err}");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    let source = InputSource::select(
+        matches.get_one::<InputSource>("input").cloned(),
+        day,
+        || "input/input.txt".into(),
+    );
+    let input = match source.read() {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("cannot read {source}: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let report = match solve::<S>(&input, &params, &[Part::One, Part::Two]) {
+        Ok(report) => report,
+        Err(err) => {
+            eprintln!("{source}: {err}");
+            eprintln!("{}", err.snippet(&input));
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if matches.get_one::<Format>("format") == Some(&Format::Json) {
+        println!("{}", json_report(day, &source, &input, &report));
+    } else {
+        for part in report.parts {
+            match part.answer {
+                Ok(answer) => println!("Part {}: {answer}", part.part),
+                Err(err) => println!("Part {}: {err}", part.part),
+            }
+        }
+    }
+
+    if let (Some(render), Some(path)) = (render, matches.get_one::<PathBuf>("render")) {
+        let scale = matches.get_one::<u16>("scale").copied().unwrap_or(4);
+        let mut parsed = S::parse(&input).expect("parsed when solving");
+        S::configure(&mut parsed, &params);
+        if let Err(err) = render(&parsed, path, scale.into()) {
+            eprintln!("cannot write {}: {err}", path.display());
+            return ExitCode::FAILURE;
+        }
+    }
+
+    ExitCode::SUCCESS
+}
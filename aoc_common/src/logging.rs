@@ -0,0 +1,22 @@
+use std::io::{self, IsTerminal};
+
+use tracing::Level;
+
+/// Sends the solvers' tracing output to stderr: warnings only by default,
+/// one more level of detail for each `-v`.
+pub fn init_logging(verbosity: u8) {
+    let level = match verbosity {
+        0 => Level::WARN,
+        1 => Level::INFO,
+        2 => Level::DEBUG,
+        _ => Level::TRACE,
+    };
+
+    // a subscriber may already be set, e.g. by a test harness
+    let _ = tracing_subscriber::fmt()
+        .with_max_level(level)
+        .with_writer(io::stderr)
+        .with_ansi(io::stderr().is_terminal())
+        .without_time()
+        .try_init();
+}
@@ -0,0 +1,150 @@
+use std::{borrow::Borrow, collections::HashMap, fmt, hash::Hash};
+
+/// Remembers the results of a recursive function by its arguments.
+///
+/// Lookups go through a borrowed form of the key, so a `Memo<String, _>` can
+/// be asked about a `&str` without allocating; the key is only turned into an
+/// owned one when a new result is stored.
+#[derive(Clone, Debug)]
+pub struct Memo<K, V> {
+    values: HashMap<K, V>,
+    stats: MemoStats,
+}
+
+/// How often a [`Memo`] already knew the answer.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct MemoStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl MemoStats {
+    /// Share of the lookups that were hits, 0 before the first one.
+    pub fn hit_rate(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            lookups => self.hits as f64 / lookups as f64,
+        }
+    }
+}
+
+impl fmt::Display for MemoStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} hits, {} misses ({:.1}% hits)",
+            self.hits,
+            self.misses,
+            self.hit_rate() * 100.0
+        )
+    }
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Memo<K, V> {
+        Memo {
+            values: HashMap::new(),
+            stats: MemoStats::default(),
+        }
+    }
+}
+
+impl<K: Hash + Eq, V> Memo<K, V> {
+    pub fn new() -> Memo<K, V> {
+        Memo::default()
+    }
+
+    /// The value remembered for `key`, counted as a hit or a miss.
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let value = self.values.get(key);
+        match value {
+            Some(_) => self.stats.hits += 1,
+            None => self.stats.misses += 1,
+        }
+        value
+    }
+
+    /// Remembers `value` for `key`, replacing what was there.
+    pub fn insert(&mut self, key: K, value: V) {
+        self.values.insert(key, value);
+    }
+
+    /// The value remembered for `key`, or else the one `compute` returns,
+    /// which is remembered from then on.
+    ///
+    /// `compute` gets the memo back, so that it can recurse through it.
+    pub fn cached<Q>(&mut self, key: &Q, compute: impl FnOnce(&mut Self) -> V) -> V
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+        V: Clone,
+    {
+        if let Some(value) = self.get(key) {
+            return value.clone();
+        }
+
+        let value = compute(self);
+        self.insert(key.to_owned(), value.clone());
+        value
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn stats(&self) -> MemoStats {
+        self.stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fibonacci(n: u64, memo: &mut Memo<u64, u64>) -> u64 {
+        if n < 2 {
+            return n;
+        }
+        memo.cached(&n, |memo| fibonacci(n - 1, memo) + fibonacci(n - 2, memo))
+    }
+
+    #[test]
+    fn cached_test() {
+        let mut memo = Memo::new();
+        assert_eq!(fibonacci(90, &mut memo), 2880067194370816120);
+        assert_eq!(memo.len(), 89);
+        // every number from 90 down to 2 is computed once, and those below 89
+        // are found once more as the second term of the one two above them
+        assert_eq!(
+            memo.stats(),
+            MemoStats {
+                hits: 87,
+                misses: 89
+            }
+        );
+
+        assert_eq!(fibonacci(90, &mut memo), 2880067194370816120);
+        assert_eq!(memo.stats().hits, 88);
+    }
+
+    #[test]
+    fn borrowed_key_test() {
+        let mut memo = Memo::<String, usize>::new();
+        let design = String::from("abcabc");
+
+        assert_eq!(memo.cached(&design[3..], |_| 1), 1);
+        assert_eq!(memo.cached(&design[..3], |_| 2), 1);
+        assert_eq!(memo.get("abc"), Some(&1));
+        assert_eq!(memo.get("abcabc"), None);
+        assert_eq!(memo.stats(), MemoStats { hits: 2, misses: 2 });
+        assert_eq!(memo.stats().to_string(), "2 hits, 2 misses (50.0% hits)");
+    }
+}
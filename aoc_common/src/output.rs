@@ -0,0 +1,122 @@
+use std::{fmt, str::FromStr, time::Duration};
+
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+use crate::{InputSource, Report};
+
+/// How the runners print their results.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Format {
+    /// A line per step, for people.
+    #[default]
+    Text,
+    /// A JSON object per solved day, on a line of its own.
+    Json,
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Format::Text => write!(f, "text"),
+            Format::Json => write!(f, "json"),
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("expected `text` or `json`, got `{s}`")),
+        }
+    }
+}
+
+/// SHA-256 of the input as lowercase hex, to tell runs on different inputs apart.
+pub fn input_hash(input: &str) -> String {
+    Sha256::digest(input.as_bytes())
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+fn nanos(duration: Duration) -> u64 {
+    duration.as_nanos().try_into().unwrap_or(u64::MAX)
+}
+
+/// The run of `day` on `input` as a single line of JSON. Answers are strings,
+/// so that no consumer rounds large numbers, and durations are nanoseconds.
+pub fn json_report(day: u8, source: &InputSource, input: &str, report: &Report) -> String {
+    let parts: Vec<Value> = report
+        .parts
+        .iter()
+        .map(|part| {
+            let (answer, error) = match &part.answer {
+                Ok(answer) => (Some(answer.clone()), None),
+                Err(err) => (None, Some(err.to_string())),
+            };
+            json!({
+                "part": part.part.number(),
+                "answer": answer,
+                "error": error,
+                "solve_ns": nanos(part.elapsed),
+            })
+        })
+        .collect();
+
+    json!({
+        "day": day,
+        "input": source.to_string(),
+        "input_sha256": input_hash(input),
+        "parse_ns": nanos(report.parse_elapsed),
+        "parts": parts,
+    })
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Part, PartReport, SolveError};
+
+    use super::*;
+
+    #[test]
+    fn json_report_test() {
+        let report = Report {
+            parse_elapsed: Duration::from_micros(12),
+            parts: vec![
+                PartReport {
+                    part: Part::One,
+                    answer: Ok("18446744073709551616".to_string()),
+                    elapsed: Duration::from_nanos(1500),
+                },
+                PartReport {
+                    part: Part::Two,
+                    answer: Err(SolveError::NotImplemented),
+                    elapsed: Duration::ZERO,
+                },
+            ],
+        };
+
+        let line = json_report(4, &InputSource::Stdin, "abc", &report);
+        assert!(!line.contains('\n'));
+        let value: Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "day": 4,
+                "input": "<stdin>",
+                "input_sha256": "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+                "parse_ns": 12000,
+                "parts": [
+                    {"part": 1, "answer": "18446744073709551616", "error": null, "solve_ns": 1500},
+                    {"part": 2, "answer": null, "error": "not solved", "solve_ns": 0},
+                ],
+            })
+        );
+    }
+}
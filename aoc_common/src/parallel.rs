@@ -0,0 +1,109 @@
+//! Loops over independent items that run on rayon's thread pool with the
+//! `parallel` feature and on the calling thread without it. Results come out
+//! in the order of the items either way.
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// `items.iter().map(f)`, collected.
+#[cfg(feature = "parallel")]
+pub fn par_map<T, R>(items: &[T], f: impl Fn(&T) -> R + Sync + Send) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+{
+    items.par_iter().map(f).collect()
+}
+
+/// `items.iter().map(f)`, collected.
+#[cfg(not(feature = "parallel"))]
+pub fn par_map<T, R>(items: &[T], f: impl Fn(&T) -> R + Sync + Send) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+{
+    items.iter().map(f).collect()
+}
+
+/// Like [`par_map`], with some scratch state for `f`, such as a cache, that
+/// `init` creates once per thread.
+#[cfg(feature = "parallel")]
+pub fn par_map_with<T, S, R>(
+    items: &[T],
+    init: impl Fn() -> S + Sync + Send,
+    f: impl Fn(&mut S, &T) -> R + Sync + Send,
+) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+{
+    items.par_iter().map_init(init, f).collect()
+}
+
+/// Like [`par_map`], with some scratch state for `f`, such as a cache, that
+/// `init` creates once per thread.
+#[cfg(not(feature = "parallel"))]
+pub fn par_map_with<T, S, R>(
+    items: &[T],
+    init: impl Fn() -> S + Sync + Send,
+    f: impl Fn(&mut S, &T) -> R + Sync + Send,
+) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+{
+    let mut state = init();
+    items.iter().map(|item| f(&mut state, item)).collect()
+}
+
+/// Sets how many threads the loops above use, for the whole process. Only
+/// takes effect before the first of them runs.
+#[cfg(feature = "parallel")]
+pub fn set_jobs(jobs: usize) -> Result<(), String> {
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(jobs)
+        .build_global()
+        .map_err(|err| format!("cannot start {jobs} threads: {err}"))
+}
+
+/// Sets how many threads the loops above use, which is always one without
+/// the `parallel` feature.
+#[cfg(not(feature = "parallel"))]
+pub fn set_jobs(jobs: usize) -> Result<(), String> {
+    match jobs {
+        1 => Ok(()),
+        _ => Err(format!(
+            "cannot run {jobs} jobs, this build lacks the `parallel` feature"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn par_map_test() {
+        let items: Vec<u64> = (0..1000).collect();
+        let squares = par_map(&items, |item| item * item);
+        assert_eq!(
+            squares,
+            items.iter().map(|item| item * item).collect::<Vec<_>>()
+        );
+
+        // each thread counts the items it saw
+        let counts = par_map_with(
+            &items,
+            || 0,
+            |seen, item| {
+                *seen += 1;
+                (*item, *seen)
+            },
+        );
+        assert!(counts
+            .iter()
+            .map(|(item, _)| *item)
+            .eq(items.iter().copied()));
+        assert!(counts.iter().all(|(_, seen)| *seen >= 1));
+    }
+}
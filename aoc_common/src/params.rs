@@ -0,0 +1,130 @@
+use std::fmt;
+
+/// A number the puzzle states besides the input, like the size of a grid.
+#[derive(Clone, Copy, Debug)]
+pub struct Param {
+    pub name: &'static str,
+    pub help: &'static str,
+    /// Value for the real puzzle; examples often use a smaller one.
+    pub default: u64,
+    /// Largest value the solver can cope with, e.g. before a grid of that
+    /// size no longer fits in memory.
+    pub max: u64,
+}
+
+/// Values for a day's [`Param`]s, starting out at their defaults.
+#[derive(Clone, Debug)]
+pub struct Params {
+    values: Vec<(Param, u64)>,
+}
+
+impl Params {
+    pub fn new(params: &[Param]) -> Params {
+        Params {
+            values: params
+                .iter()
+                .map(|param| (*param, param.default))
+                .collect(),
+        }
+    }
+
+    /// Sets one of the day's parameters; all of them are positive and at
+    /// most their [`Param::max`].
+    pub fn set(&mut self, name: &str, value: u64) -> Result<(), String> {
+        let known: Vec<&str> = self.values.iter().map(|(param, _)| param.name).collect();
+        let Some((param, slot)) = self.values.iter_mut().find(|(param, _)| param.name == name)
+        else {
+            if known.is_empty() {
+                return Err(format!("unknown parameter `{name}`, this day takes none"));
+            }
+            return Err(format!(
+                "unknown parameter `{name}`, expected one of {}",
+                known.join(", ")
+            ));
+        };
+
+        if value == 0 {
+            return Err(format!("parameter `{name}` must be at least 1"));
+        }
+        if value > param.max {
+            return Err(format!("parameter `{name}` must be at most {}", param.max));
+        }
+        *slot = value;
+        Ok(())
+    }
+
+    /// Sets a parameter from a `name=value` assignment.
+    pub fn assign(&mut self, assignment: &str) -> Result<(), String> {
+        let (name, value) = assignment
+            .split_once('=')
+            .ok_or_else(|| format!("expected `<name>=<value>`, got `{assignment}`"))?;
+        let value = value
+            .trim()
+            .parse()
+            .map_err(|_| format!("parameter `{name}` must be a number, got `{value}`"))?;
+        self.set(name.trim(), value)
+    }
+
+    /// Panics if the day does not declare a parameter called `name`.
+    pub fn get(&self, name: &str) -> u64 {
+        self.values
+            .iter()
+            .find(|(param, _)| param.name == name)
+            .map(|(_, value)| *value)
+            .unwrap_or_else(|| panic!("undeclared parameter `{name}`"))
+    }
+}
+
+/// All values as `name=value` assignments, in the order the day declares them.
+impl fmt::Display for Params {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, (param, value)) in self.values.iter().enumerate() {
+            if idx > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{}={value}", param.name)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PARAMS: &[Param] = &[
+        Param {
+            name: "width",
+            help: "",
+            default: 101,
+            max: 1000,
+        },
+        Param {
+            name: "height",
+            help: "",
+            default: 103,
+            max: 1000,
+        },
+    ];
+
+    #[test]
+    fn assign_test() {
+        let mut params = Params::new(PARAMS);
+        assert_eq!(params.get("width"), 101);
+
+        params.assign("width=11").unwrap();
+        params.set("height", 7).unwrap();
+        assert_eq!((params.get("width"), params.get("height")), (11, 7));
+
+        assert!(params.assign("depth=3").is_err());
+        assert!(params.assign("width").is_err());
+        assert!(params.assign("width=x").is_err());
+        assert!(params.set("width", 0).is_err());
+        assert!(params.set("width", 1001).is_err());
+        params.set("width", 1000).unwrap();
+        params.set("width", 11).unwrap();
+        assert_eq!(params.get("width"), 11);
+        assert_eq!(params.to_string(), "width=11 height=7");
+        assert_eq!(Params::new(&[]).to_string(), "");
+    }
+}
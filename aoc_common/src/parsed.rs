@@ -0,0 +1,104 @@
+use std::time::Instant;
+
+use serde::{de::DeserializeOwned, Serialize};
+use tracing::{info, info_span};
+
+use crate::{format_duration, solution::solve_parts, Params, ParseError, Part, Report, Solution};
+
+/// Parses `input` and applies `params` like [`solve`](crate::solve) does,
+/// then writes the result out as JSON instead of solving it.
+pub fn dump_parsed<S: Solution>(input: &str, params: &Params) -> Result<String, ParseError>
+where
+    S::Input: Serialize,
+{
+    let mut parsed = S::parse(input)?;
+    S::configure(&mut parsed, params);
+    Ok(serde_json::to_string(&parsed)
+        .expect("parsed inputs only have maps with string or number keys"))
+}
+
+/// Reads back what [`dump_parsed`] wrote, with the parameters it was made with.
+pub fn load_parsed<S: Solution>(json: &str) -> Result<S::Input, ParseError>
+where
+    S::Input: DeserializeOwned,
+{
+    serde_json::from_str(json).map_err(|err| {
+        // the position is in the error already
+        let message = err.to_string();
+        let position = format!(" at line {} column {}", err.line(), err.column());
+        let message = message.strip_suffix(&position).unwrap_or(&message);
+        let expected = format!("a parsed input as written by --dump-parsed ({message})");
+        ParseError::new(err.line(), err.column(), expected)
+    })
+}
+
+/// Like [`solve`](crate::solve), but loads `json` from [`dump_parsed`]
+/// instead of parsing the puzzle input.
+pub fn solve_parsed<S: Solution>(json: &str, parts: &[Part]) -> Result<Report, ParseError>
+where
+    S::Input: DeserializeOwned,
+{
+    let start = Instant::now();
+    let parsed = info_span!("load").in_scope(|| load_parsed::<S>(json))?;
+    let load_elapsed = start.elapsed();
+    info!(elapsed = %format_duration(load_elapsed), "loaded");
+
+    Ok(solve_parts::<S>(&parsed, load_elapsed, parts))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SolveError;
+
+    /// Sums the numbers on the first line, and multiplies them by `factor`.
+    struct Numbers;
+
+    impl Solution for Numbers {
+        type Input = (Vec<u64>, u64);
+        type Answer1 = u64;
+        type Answer2 = u64;
+
+        const PARAMS: &'static [crate::Param] = &[crate::Param {
+            name: "factor",
+            help: "",
+            default: 1,
+            max: u64::MAX,
+        }];
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            let numbers = input
+                .split_whitespace()
+                .map(|token| crate::parse_number(input, token))
+                .collect::<Result<_, _>>()?;
+            Ok((numbers, 1))
+        }
+
+        fn configure(input: &mut Self::Input, params: &Params) {
+            input.1 = params.get("factor");
+        }
+
+        fn part1((numbers, _): &Self::Input) -> Result<u64, SolveError> {
+            Ok(numbers.iter().sum())
+        }
+
+        fn part2((numbers, factor): &Self::Input) -> Result<u64, SolveError> {
+            Ok(numbers.iter().sum::<u64>() * factor)
+        }
+    }
+
+    #[test]
+    fn round_trip_test() {
+        let mut params = Params::new(Numbers::PARAMS);
+        params.set("factor", 3).unwrap();
+        let json = dump_parsed::<Numbers>("1 2 3", &params).unwrap();
+        assert_eq!(json, "[[1,2,3],3]");
+
+        let report = solve_parsed::<Numbers>(&json, &[Part::One, Part::Two]).unwrap();
+        let answers: Vec<_> = report.parts.into_iter().map(|part| part.answer).collect();
+        assert_eq!(answers, [Ok("6".to_string()), Ok("18".to_string())]);
+
+        let err = load_parsed::<Numbers>("[[1, 2,\n \"3\"], 3]").unwrap_err();
+        assert_eq!((err.line(), err.column()), (2, 4));
+    }
+}
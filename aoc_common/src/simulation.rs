@@ -0,0 +1,154 @@
+/// Where a simulation starts repeating itself, in steps from where it is now.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Cycle {
+    /// Steps before the first state that comes back.
+    pub start: usize,
+    /// Steps between two visits of the same state.
+    pub period: usize,
+}
+
+/// Something that changes one step at a time, such as the guard of day 6 or
+/// the robots of day 14.
+pub trait Simulation {
+    /// What has to come back for the simulation to be in a cycle.
+    type State: PartialEq;
+
+    fn state(&self) -> Self::State;
+
+    /// Moves on by one step; false once the simulation has ended, in which
+    /// case nothing changes.
+    fn step(&mut self) -> bool;
+
+    /// Moves on by `steps` steps, or fewer if the simulation ends before.
+    /// Returns the number of steps taken.
+    fn run_for(&mut self, steps: usize) -> usize {
+        (0..steps).take_while(|_| self.step()).count()
+    }
+
+    /// Steps until `done` holds, which may be right away. Returns the number
+    /// of steps taken, or `None` if the simulation ended first.
+    fn run_until(&mut self, mut done: impl FnMut(&Self) -> bool) -> Option<usize>
+    where
+        Self: Sized,
+    {
+        let mut steps = 0;
+        while !done(self) {
+            if !self.step() {
+                return None;
+            }
+            steps += 1;
+        }
+        Some(steps)
+    }
+
+    /// Finds the cycle the simulation ends up in with Brent's algorithm, which
+    /// only ever keeps two copies of it around. `None` if it ends instead.
+    fn find_cycle(&self) -> Option<Cycle>
+    where
+        Self: Clone,
+    {
+        // the hare runs ahead, the tortoise waits for it at powers of two
+        let mut tortoise = self.clone();
+        let mut hare = self.clone();
+        let (mut power, mut period) = (1, 1);
+        if !hare.step() {
+            return None;
+        }
+        while tortoise.state() != hare.state() {
+            if power == period {
+                tortoise = hare.clone();
+                power *= 2;
+                period = 0;
+            }
+            if !hare.step() {
+                return None;
+            }
+            period += 1;
+        }
+
+        // with the hare a period ahead, the two meet where the cycle starts
+        let mut tortoise = self.clone();
+        let mut hare = self.clone();
+        hare.run_for(period);
+        let mut start = 0;
+        while tortoise.state() != hare.state() {
+            tortoise.step();
+            hare.step();
+            start += 1;
+        }
+
+        Some(Cycle { start, period })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Counts up from 0 and wraps from `end - 1` back to `wrap`, or stops at
+    /// `end` without one.
+    #[derive(Clone)]
+    struct Counter {
+        count: usize,
+        end: usize,
+        wrap: Option<usize>,
+    }
+
+    impl Simulation for Counter {
+        type State = usize;
+
+        fn state(&self) -> usize {
+            self.count
+        }
+
+        fn step(&mut self) -> bool {
+            match self.wrap {
+                _ if self.count + 1 < self.end => self.count += 1,
+                Some(wrap) => self.count = wrap,
+                None if self.count + 1 == self.end => self.count += 1,
+                None => return false,
+            }
+            true
+        }
+    }
+
+    #[test]
+    fn find_cycle_test() {
+        for (end, wrap, cycle) in [
+            (8, 3, (3, 5)),
+            (5, 0, (0, 5)),
+            (1, 0, (0, 1)),
+            (9, 8, (8, 1)),
+        ] {
+            let counter = Counter {
+                count: 0,
+                end,
+                wrap: Some(wrap),
+            };
+            let (start, period) = cycle;
+            assert_eq!(counter.find_cycle(), Some(Cycle { start, period }));
+        }
+
+        let counter = Counter {
+            count: 0,
+            end: 8,
+            wrap: None,
+        };
+        assert_eq!(counter.find_cycle(), None);
+    }
+
+    #[test]
+    fn run_test() {
+        let mut counter = Counter {
+            count: 0,
+            end: 8,
+            wrap: None,
+        };
+        assert_eq!(counter.run_for(3), 3);
+        assert_eq!(counter.run_until(|counter| counter.count == 3), Some(0));
+        assert_eq!(counter.run_until(|counter| counter.count == 5), Some(2));
+        assert_eq!(counter.run_for(10), 3);
+        assert_eq!(counter.state(), 8);
+        assert_eq!(counter.run_until(|counter| counter.count == 9), None);
+    }
+}
@@ -0,0 +1,129 @@
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use tracing::{info, info_span};
+
+use crate::{Param, Params, ParseError, SolveError};
+
+/// A single day's puzzle: the input is parsed once and both parts are solved from it.
+pub trait Solution {
+    type Input;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    /// Numbers the puzzle states besides the input, like the size of a grid.
+    const PARAMS: &'static [Param] = &[];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+    /// Applies values for [`Self::PARAMS`]; `parse` alone must already set
+    /// their defaults.
+    fn configure(_input: &mut Self::Input, _params: &Params) {}
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, SolveError>;
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, SolveError>;
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(format!("expected part 1 or 2, got `{s}`")),
+        }
+    }
+}
+
+pub struct PartReport {
+    pub part: Part,
+    pub answer: Result<String, SolveError>,
+    pub elapsed: Duration,
+}
+
+pub struct Report {
+    pub parse_elapsed: Duration,
+    pub parts: Vec<PartReport>,
+}
+
+/// Parses `input`, applies `params` and solves the requested parts, timing each step.
+pub fn solve<S: Solution>(
+    input: &str,
+    params: &Params,
+    parts: &[Part],
+) -> Result<Report, ParseError> {
+    let start = Instant::now();
+    let mut parsed = info_span!("parse").in_scope(|| S::parse(input))?;
+    S::configure(&mut parsed, params);
+    let parse_elapsed = start.elapsed();
+    info!(elapsed = %format_duration(parse_elapsed), "parsed");
+
+    Ok(solve_parts::<S>(&parsed, parse_elapsed, parts))
+}
+
+/// Solves the requested parts of an input that is already parsed.
+pub(crate) fn solve_parts<S: Solution>(
+    parsed: &S::Input,
+    parse_elapsed: Duration,
+    parts: &[Part],
+) -> Report {
+    let parts = parts
+        .iter()
+        .map(|part| {
+            let _span = info_span!("part", part = %part).entered();
+            let start = Instant::now();
+            let answer = match part {
+                Part::One => S::part1(parsed).map(|answer| answer.to_string()),
+                Part::Two => S::part2(parsed).map(|answer| answer.to_string()),
+            };
+            let elapsed = start.elapsed();
+            info!(elapsed = %format_duration(elapsed), "solved");
+            PartReport {
+                part: *part,
+                answer,
+                elapsed,
+            }
+        })
+        .collect();
+
+    Report {
+        parse_elapsed,
+        parts,
+    }
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let micros = duration.as_micros();
+    if micros < 1000 {
+        format!("{micros}µs")
+    } else if micros < 1_000_000 {
+        format!("{:.2}ms", micros as f64 / 1000.0)
+    } else {
+        format!("{:.2}s", duration.as_secs_f64())
+    }
+}
@@ -0,0 +1,18 @@
+//! Two columns of location ids.
+
+use std::fmt::Write;
+
+use crate::Rng;
+
+pub const PUZZLE_SIZE: usize = 1000;
+
+/// `size` lines of two five digit ids.
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    let mut out = String::new();
+    for _ in 0..size {
+        let left = rng.range(10_000, 100_000);
+        let right = rng.range(10_000, 100_000);
+        writeln!(out, "{left}   {right}").unwrap();
+    }
+    out
+}
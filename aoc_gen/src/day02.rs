@@ -0,0 +1,25 @@
+//! Reports of levels.
+
+use std::fmt::Write;
+
+use crate::Rng;
+
+pub const PUZZLE_SIZE: usize = 1000;
+
+/// `size` reports of 5 to 8 levels, most of them safe or nearly so.
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    let mut out = String::new();
+    for _ in 0..size {
+        let mut level = rng.range(10, 90) as i64;
+        let direction = if rng.range(0, 2) == 0 { 1 } else { -1 };
+        let mut report = vec![level];
+        for _ in 1..rng.range(5, 9) {
+            // mostly safe steps, with the occasional bad one
+            level += direction * rng.range(0, 5) as i64;
+            report.push(level);
+        }
+        let line: Vec<String> = report.iter().map(i64::to_string).collect();
+        writeln!(out, "{}", line.join(" ")).unwrap();
+    }
+    out
+}
@@ -0,0 +1,23 @@
+//! Corrupted memory with `mul`, `do` and `don't` instructions.
+
+use std::fmt::Write;
+
+use crate::Rng;
+
+pub const PUZZLE_SIZE: usize = 18_000;
+
+/// About `size` characters of instructions, broken ones and noise.
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    let mut out = String::new();
+    while out.len() < size {
+        match rng.range(0, 10) {
+            0..=3 => write!(out, "mul({},{})", rng.range(1, 1000), rng.range(1, 1000)).unwrap(),
+            4 => out.push_str("do()"),
+            5 => out.push_str("don't()"),
+            6 => write!(out, "mul({},{}]", rng.range(1, 1000), rng.range(1, 1000)).unwrap(),
+            _ => out.push(rng.pick(&['x', '%', '&', '[', ']', '!', '@', '^', '(', ')', ',', ' '])),
+        }
+    }
+    out.push('\n');
+    out
+}
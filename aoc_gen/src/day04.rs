@@ -0,0 +1,10 @@
+//! Word search letters.
+
+use crate::{grid, Rng};
+
+pub const PUZZLE_SIZE: usize = 140;
+
+/// `size` x `size` letters of `XMAS`.
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    grid(rng, size, |rng| rng.pick(&['X', 'M', 'A', 'S']))
+}
@@ -0,0 +1,32 @@
+//! Page ordering rules and updates.
+
+use std::fmt::Write;
+
+use crate::Rng;
+
+pub const PUZZLE_SIZE: usize = 200;
+
+/// Rules ordering 49 pages, then `size` updates of an odd number of them.
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    let mut pages: Vec<u64> = (10..100).collect();
+    rng.shuffle(&mut pages);
+    pages.truncate(49);
+
+    // one rule for every pair, so any update can be ordered
+    let mut out = String::new();
+    for (i, before) in pages.iter().enumerate() {
+        for after in &pages[i + 1..] {
+            writeln!(out, "{before}|{after}").unwrap();
+        }
+    }
+
+    out.push('\n');
+    for _ in 0..size {
+        let mut update = pages.clone();
+        rng.shuffle(&mut update);
+        update.truncate(2 * rng.range(2, 12) as usize + 1);
+        let line: Vec<String> = update.iter().map(u64::to_string).collect();
+        writeln!(out, "{}", line.join(",")).unwrap();
+    }
+    out
+}
@@ -0,0 +1,25 @@
+//! Lab map with obstacles and the guard.
+
+use crate::{grid, Rng};
+
+pub const PUZZLE_SIZE: usize = 130;
+
+/// `size` x `size` map, about one tile in twenty an obstacle, with the guard
+/// somewhere free.
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let mut map: Vec<Vec<char>> = grid(rng, size, |rng| if rng.one_in(20) { '#' } else { '.' })
+        .lines()
+        .map(|line| line.chars().collect())
+        .collect();
+
+    let (y, x) = (
+        rng.range(0, size as u64) as usize,
+        rng.range(0, size as u64) as usize,
+    );
+    map[y][x] = '^';
+
+    map.iter()
+        .map(|row| row.iter().collect::<String>() + "\n")
+        .collect()
+}
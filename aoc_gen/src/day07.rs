@@ -0,0 +1,27 @@
+//! Calibration equations.
+
+use std::fmt::Write;
+
+use crate::Rng;
+
+pub const PUZZLE_SIZE: usize = 200;
+
+/// `size` equations of 3 to 8 numbers below 100, most of them solvable.
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    let mut out = String::new();
+    for _ in 0..size {
+        let numbers: Vec<u64> = (0..rng.range(3, 9)).map(|_| rng.range(1, 100)).collect();
+        // combine with random operators, so most equations can be solved; at
+        // most two digits are added per number, which keeps the target in a u64
+        let target = numbers[1..]
+            .iter()
+            .fold(numbers[0], |acc, &n| match rng.range(0, 3) {
+                0 => acc + n,
+                1 => acc * n,
+                _ => format!("{acc}{n}").parse().unwrap(),
+            });
+        let line: Vec<String> = numbers.iter().map(u64::to_string).collect();
+        writeln!(out, "{target}: {}", line.join(" ")).unwrap();
+    }
+    out
+}
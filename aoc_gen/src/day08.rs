@@ -0,0 +1,13 @@
+//! Antenna map.
+
+use crate::{grid, Rng};
+
+pub const PUZZLE_SIZE: usize = 50;
+
+/// `size` x `size` map with antennas of a few frequencies.
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    grid(rng, size, |rng| match rng.range(0, 12) {
+        0 => rng.pick(&['0', 'a', 'A', 'z', 'Z', '9']),
+        _ => '.',
+    })
+}
@@ -0,0 +1,18 @@
+//! Disk map.
+
+use crate::Rng;
+
+pub const PUZZLE_SIZE: usize = 19_999;
+
+/// `size` digits, alternating between file and gap lengths.
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    let mut out: String = (0..size)
+        .map(|i| {
+            // files are never empty, gaps may be
+            let low = if i % 2 == 0 { 1 } else { 0 };
+            char::from_digit(rng.range(low, 10) as u32, 10).unwrap()
+        })
+        .collect();
+    out.push('\n');
+    out
+}
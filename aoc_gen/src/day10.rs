@@ -0,0 +1,12 @@
+//! Topographic map.
+
+use crate::{grid, Rng};
+
+pub const PUZZLE_SIZE: usize = 50;
+
+/// `size` x `size` heights.
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    grid(rng, size, |rng| {
+        char::from_digit(rng.range(0, 10) as u32, 10).unwrap()
+    })
+}
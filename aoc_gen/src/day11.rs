@@ -0,0 +1,13 @@
+//! Stones.
+
+use crate::Rng;
+
+pub const PUZZLE_SIZE: usize = 8;
+
+/// `size` stones below a million.
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    let stones: Vec<String> = (0..size)
+        .map(|_| rng.range(0, 1_000_000).to_string())
+        .collect();
+    stones.join(" ") + "\n"
+}
@@ -0,0 +1,10 @@
+//! Garden plots.
+
+use crate::{grid, Rng};
+
+pub const PUZZLE_SIZE: usize = 140;
+
+/// `size` x `size` plots of five kinds of plants.
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    grid(rng, size, |rng| rng.pick(&['A', 'B', 'C', 'D', 'E']))
+}
@@ -0,0 +1,51 @@
+//! Claw machines.
+
+use crate::Rng;
+
+pub const PUZZLE_SIZE: usize = 320;
+
+/// `size` machines, about half of them with a reachable prize.
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    let mut machines = Vec::new();
+    for _ in 0..size {
+        let (a_x, a_y) = (rng.range(10, 100), rng.range(10, 100));
+        let (b_x, b_y) = (rng.range(10, 100), rng.range(10, 100));
+        let (a, b) = (rng.range(1, 100), rng.range(1, 100));
+        let offset = rng.range(0, 2);
+        machines.push(format_machine((a_x, a_y), (b_x, b_y), (a, b), offset));
+    }
+    machines.join("\n")
+}
+
+/// Machines like those of [`input`], built from proptest values instead of
+/// a seed so that failures shrink towards fewer machines with smaller
+/// buttons and prizes.
+#[cfg(feature = "proptest")]
+pub fn machines(
+    counts: std::ops::RangeInclusive<usize>,
+) -> impl proptest::strategy::Strategy<Value = String> {
+    use proptest::prelude::*;
+
+    let button = (10..100u64, 10..100u64);
+    let machine = (button.clone(), button, (1..100u64, 1..100u64), 0..2u64)
+        .prop_map(|(button_a, button_b, presses, offset)| {
+            format_machine(button_a, button_b, presses, offset)
+        });
+    prop::collection::vec(machine, counts).prop_map(|machines| machines.join("\n"))
+}
+
+/// The prize is `presses` of `A` and `B` away, moved `offset` further
+/// along X to make it unreachable.
+fn format_machine(
+    button_a: (u64, u64),
+    button_b: (u64, u64),
+    (a, b): (u64, u64),
+    offset: u64,
+) -> String {
+    let ((a_x, a_y), (b_x, b_y)) = (button_a, button_b);
+    format!(
+        "Button A: X+{a_x}, Y+{a_y}\nButton B: X+{b_x}, Y+{b_y}\nPrize: X={}, Y={}\n",
+        a * a_x + b * b_x + offset,
+        a * a_y + b * b_y,
+    )
+}
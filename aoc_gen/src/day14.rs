@@ -0,0 +1,28 @@
+//! Robot positions and velocities.
+
+use std::fmt::Write;
+
+use crate::Rng;
+
+pub const PUZZLE_SIZE: usize = 500;
+
+/// The puzzle's space, also the default of the `width` and `height` parameters.
+pub const WIDTH: u64 = 101;
+pub const HEIGHT: u64 = 103;
+
+/// `size` robots in the puzzle's 101 x 103 space.
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    robots(rng, size, WIDTH, HEIGHT)
+}
+
+/// `count` robots in a `width` x `height` space.
+pub fn robots(rng: &mut Rng, count: usize, width: u64, height: u64) -> String {
+    let mut out = String::new();
+    for _ in 0..count {
+        let (p_x, p_y) = (rng.range(0, width), rng.range(0, height));
+        let v_x = rng.range(0, 199) as i64 - 99;
+        let v_y = rng.range(0, 199) as i64 - 99;
+        writeln!(out, "p={p_x},{p_y} v={v_x},{v_y}").unwrap();
+    }
+    out
+}
@@ -0,0 +1,38 @@
+//! Warehouse map and the robot's moves.
+
+use crate::Rng;
+
+pub const PUZZLE_SIZE: usize = 50;
+
+/// `size` x `size` warehouse walled in all around, with boxes, more walls and
+/// the robot inside, then `400 * size` moves in lines of up to 1000.
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(3);
+    let mut map = vec![vec!['#'; size]; size];
+    for row in &mut map[1..size - 1] {
+        for tile in &mut row[1..size - 1] {
+            *tile = match rng.range(0, 20) {
+                0 | 1 => '#',
+                2..=6 => 'O',
+                _ => '.',
+            };
+        }
+    }
+    let (y, x) = (rng.range(1, size as u64 - 1), rng.range(1, size as u64 - 1));
+    map[y as usize][x as usize] = '@';
+
+    let mut out: String = map
+        .iter()
+        .map(|row| row.iter().collect::<String>() + "\n")
+        .collect();
+    out.push('\n');
+
+    let moves: Vec<char> = (0..400 * size)
+        .map(|_| rng.pick(&['^', 'v', '<', '>']))
+        .collect();
+    for line in moves.chunks(1000) {
+        out.extend(line);
+        out.push('\n');
+    }
+    out
+}
@@ -0,0 +1,57 @@
+//! Reindeer maze.
+
+use crate::Rng;
+
+pub const PUZZLE_SIZE: usize = 141;
+
+/// `size` x `size` maze, rounded up to an odd size, with `S` in the bottom
+/// left and `E` in the top right corner.
+///
+/// The maze is carved as a spanning tree over the tiles at odd coordinates,
+/// so there is always a way from `S` to `E`; knocking out some more walls
+/// then adds loops, and with them paths of equal cost.
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(3) | 1;
+    let mut maze = vec![vec!['#'; size]; size];
+
+    // depth first search with an explicit stack, the puzzle sized maze is too
+    // deep for recursion
+    let mut stack: Vec<(usize, usize)> = vec![(1, 1)];
+    maze[1][1] = '.';
+    while let Some(&(y, x)) = stack.last() {
+        let mut next: Vec<(usize, usize)> = [(0, 2), (2, 0), (0, -2), (-2, 0)]
+            .into_iter()
+            .filter_map(|(dy, dx): (isize, isize)| {
+                let (next_y, next_x) = (y.checked_add_signed(dy)?, x.checked_add_signed(dx)?);
+                (next_y < size - 1 && next_x < size - 1 && maze[next_y][next_x] == '#')
+                    .then_some((next_y, next_x))
+            })
+            .collect();
+        if next.is_empty() {
+            stack.pop();
+            continue;
+        }
+
+        rng.shuffle(&mut next);
+        let (next_y, next_x) = next[0];
+        maze[(y + next_y) / 2][(x + next_x) / 2] = '.';
+        maze[next_y][next_x] = '.';
+        stack.push((next_y, next_x));
+    }
+
+    for y in 1..size - 1 {
+        for x in 1..size - 1 {
+            let joins_horizontally = maze[y][x - 1] == '.' && maze[y][x + 1] == '.';
+            let joins_vertically = maze[y - 1][x] == '.' && maze[y + 1][x] == '.';
+            if maze[y][x] == '#' && (joins_horizontally || joins_vertically) && rng.one_in(10) {
+                maze[y][x] = '.';
+            }
+        }
+    }
+
+    maze[size - 2][1] = 'S';
+    maze[1][size - 2] = 'E';
+    maze.iter()
+        .map(|row| row.iter().collect::<String>() + "\n")
+        .collect()
+}
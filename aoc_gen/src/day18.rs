@@ -0,0 +1,25 @@
+//! Falling bytes.
+
+use std::fmt::Write;
+
+use crate::Rng;
+
+/// The puzzle's largest coordinate, also the default of the `max-coord` parameter.
+pub const PUZZLE_SIZE: usize = 70;
+
+/// Bytes falling on seven in ten tiles of the space from `0,0` to
+/// `size,size`, never on those two corners.
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    let mut tiles: Vec<(usize, usize)> = (0..=size)
+        .flat_map(|y| (0..=size).map(move |x| (y, x)))
+        .filter(|&tile| tile != (0, 0) && tile != (size, size))
+        .collect();
+    rng.shuffle(&mut tiles);
+    tiles.truncate(tiles.len() * 7 / 10);
+
+    let mut out = String::new();
+    for (y, x) in tiles {
+        writeln!(out, "{x},{y}").unwrap();
+    }
+    out
+}
@@ -0,0 +1,28 @@
+//! Towel patterns and designs.
+
+use std::fmt::Write;
+
+use crate::Rng;
+
+pub const PUZZLE_SIZE: usize = 400;
+
+/// `size` towels of up to eight stripes, then `size` designs of 20 to 60.
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    let colors = ['w', 'u', 'b', 'r', 'g'];
+    let mut towels = Vec::new();
+    // there are only so many distinct towels
+    while towels.len() < size.clamp(1, 10_000) {
+        let towel: String = (0..rng.range(1, 9)).map(|_| rng.pick(&colors)).collect();
+        // leave out single `g` towels so that some designs are impossible
+        if towel != "g" && !towels.contains(&towel) {
+            towels.push(towel);
+        }
+    }
+
+    let mut out = towels.join(", ") + "\n\n";
+    for _ in 0..size {
+        let design: String = (0..rng.range(20, 61)).map(|_| rng.pick(&colors)).collect();
+        writeln!(out, "{design}").unwrap();
+    }
+    out
+}
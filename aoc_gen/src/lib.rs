@@ -0,0 +1,93 @@
+//! Random but valid puzzle inputs of any size, for benchmarks and property
+//! tests. The same seed and size always give the same input.
+//!
+//! Every `dayNN` module has an `input` generator and the `PUZZLE_SIZE` that
+//! makes its output about as big as a real puzzle input.
+
+mod rng;
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day18;
+pub mod day19;
+
+pub use rng::Rng;
+
+/// Generates an input of the given size, in whatever unit suits the day.
+pub type Generator = fn(&mut Rng, usize) -> String;
+
+/// `size` x `size` cells, one row per line.
+fn grid(rng: &mut Rng, size: usize, mut cell: impl FnMut(&mut Rng) -> char) -> String {
+    let mut out = String::new();
+    for _ in 0..size {
+        out.extend((0..size).map(|_| cell(rng)));
+        out.push('\n');
+    }
+    out
+}
+
+/// Inputs from `generate` with a size in `sizes`; failures shrink towards
+/// smaller inputs from the same seed. Shrinking the seed would only give an
+/// unrelated input, so days with a structural strategy of their own, like
+/// [`day13::machines`], shrink better with that.
+#[cfg(feature = "proptest")]
+pub fn inputs(
+    generate: Generator,
+    sizes: std::ops::RangeInclusive<usize>,
+) -> impl proptest::strategy::Strategy<Value = String> {
+    use proptest::prelude::*;
+
+    (sizes, any::<u64>().no_shrink())
+        .prop_map(move |(size, seed)| generate(&mut crate::Rng::new(seed), size))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seed_test() {
+        let first = day16::input(&mut Rng::new(7), 21);
+        assert_eq!(first, day16::input(&mut Rng::new(7), 21));
+        assert_ne!(first, day16::input(&mut Rng::new(8), 21));
+        assert_eq!(first.lines().count(), 21);
+    }
+
+    #[test]
+    fn range_test() {
+        let mut rng = Rng::new(1);
+        assert_eq!(rng.range(5, 6), 5);
+        assert_eq!(rng.range(5, 5), 5);
+        assert_eq!(rng.range(5, 4), 5);
+        assert!((0..100).all(|_| (3..7).contains(&rng.range(3, 7))));
+    }
+
+    #[test]
+    fn small_sizes_test() {
+        let generators: [Generator; 18] = [
+            day01::input, day02::input, day03::input, day04::input, day05::input, day06::input,
+            day07::input, day08::input, day09::input, day10::input, day11::input, day12::input,
+            day13::input, day14::input, day15::input, day16::input, day18::input, day19::input,
+        ];
+        // generators must cope with sizes too small for a sensible puzzle
+        for generate in generators {
+            for (size, seed) in (0..4).flat_map(|size| (0..20).map(move |seed| (size, seed))) {
+                generate(&mut Rng::new(seed), size);
+            }
+        }
+    }
+}
@@ -0,0 +1,314 @@
+use std::{
+    fmt,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+};
+
+use crate::Idx;
+
+/// Point on the plane, `y` growing downwards and `x` to the right like grid rows and columns.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Pos {
+    pub y: i64,
+    pub x: i64,
+}
+
+/// Displacement between two [`Pos`]itions.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Vec2 {
+    pub y: i64,
+    pub x: i64,
+}
+
+impl Pos {
+    pub const fn new(y: i64, x: i64) -> Pos {
+        Pos { y, x }
+    }
+
+    pub fn manhattan(self, other: Pos) -> u64 {
+        (other - self).manhattan_len()
+    }
+
+    pub fn in_bounds(self, width: usize, height: usize) -> bool {
+        self.to_idx()
+            .is_some_and(|(row, col)| row < height && col < width)
+    }
+
+    /// `self + delta`, or `None` if that leaves a `width` x `height` area.
+    pub fn checked_offset(self, delta: Vec2, width: usize, height: usize) -> Option<Pos> {
+        let pos = Pos::new(self.y.checked_add(delta.y)?, self.x.checked_add(delta.x)?);
+        pos.in_bounds(width, height).then_some(pos)
+    }
+
+    /// Grid index `(row, col)`, or `None` for negative coordinates.
+    pub fn to_idx(self) -> Option<Idx> {
+        Some((usize::try_from(self.y).ok()?, usize::try_from(self.x).ok()?))
+    }
+}
+
+impl From<Idx> for Pos {
+    fn from((row, col): Idx) -> Pos {
+        Pos::new(row as i64, col as i64)
+    }
+}
+
+/// Formats as `x,y`, the way puzzles write coordinates.
+impl fmt::Display for Pos {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+impl Vec2 {
+    pub const fn new(y: i64, x: i64) -> Vec2 {
+        Vec2 { y, x }
+    }
+
+    pub fn manhattan_len(self) -> u64 {
+        self.y.unsigned_abs() + self.x.unsigned_abs()
+    }
+
+    /// Quarter turn clockwise (on screen, with `y` pointing down).
+    pub fn rotate_right(self) -> Vec2 {
+        Vec2::new(self.x, -self.y)
+    }
+
+    /// Quarter turn counter-clockwise (on screen, with `y` pointing down).
+    pub fn rotate_left(self) -> Vec2 {
+        Vec2::new(-self.x, self.y)
+    }
+}
+
+impl Add<Vec2> for Pos {
+    type Output = Pos;
+
+    fn add(self, rhs: Vec2) -> Pos {
+        Pos::new(self.y + rhs.y, self.x + rhs.x)
+    }
+}
+
+impl AddAssign<Vec2> for Pos {
+    fn add_assign(&mut self, rhs: Vec2) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub<Vec2> for Pos {
+    type Output = Pos;
+
+    fn sub(self, rhs: Vec2) -> Pos {
+        Pos::new(self.y - rhs.y, self.x - rhs.x)
+    }
+}
+
+impl SubAssign<Vec2> for Pos {
+    fn sub_assign(&mut self, rhs: Vec2) {
+        *self = *self - rhs;
+    }
+}
+
+impl Sub for Pos {
+    type Output = Vec2;
+
+    fn sub(self, rhs: Pos) -> Vec2 {
+        Vec2::new(self.y - rhs.y, self.x - rhs.x)
+    }
+}
+
+impl Add for Vec2 {
+    type Output = Vec2;
+
+    fn add(self, rhs: Vec2) -> Vec2 {
+        Vec2::new(self.y + rhs.y, self.x + rhs.x)
+    }
+}
+
+impl Sub for Vec2 {
+    type Output = Vec2;
+
+    fn sub(self, rhs: Vec2) -> Vec2 {
+        Vec2::new(self.y - rhs.y, self.x - rhs.x)
+    }
+}
+
+impl Mul<i64> for Vec2 {
+    type Output = Vec2;
+
+    fn mul(self, rhs: i64) -> Vec2 {
+        Vec2::new(self.y * rhs, self.x * rhs)
+    }
+}
+
+impl Neg for Vec2 {
+    type Output = Vec2;
+
+    fn neg(self) -> Vec2 {
+        Vec2::new(-self.y, -self.x)
+    }
+}
+
+/// One of the four orthogonal directions, listed clockwise from `Up`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub fn vec(self) -> Vec2 {
+        match self {
+            Direction::Up => Vec2::new(-1, 0),
+            Direction::Right => Vec2::new(0, 1),
+            Direction::Down => Vec2::new(1, 0),
+            Direction::Left => Vec2::new(0, -1),
+        }
+    }
+
+    /// Turns clockwise by `quarter_turns`; negative values turn counter-clockwise.
+    pub fn rotate(self, quarter_turns: isize) -> Direction {
+        Direction::ALL[(self as isize + quarter_turns).rem_euclid(4) as usize]
+    }
+
+    pub fn turn_right(self) -> Direction {
+        self.rotate(1)
+    }
+
+    pub fn turn_left(self) -> Direction {
+        self.rotate(-1)
+    }
+
+    pub fn reverse(self) -> Direction {
+        self.rotate(2)
+    }
+
+    /// Clockwise quarter turns (0 to 3) needed to face `other`.
+    pub fn turns_to(self, other: Direction) -> usize {
+        (other as usize + 4 - self as usize) % 4
+    }
+
+    /// Parses the arrows `^`, `>`, `v` and `<`.
+    pub fn from_arrow(chr: char) -> Option<Direction> {
+        match chr {
+            '^' => Some(Direction::Up),
+            '>' => Some(Direction::Right),
+            'v' => Some(Direction::Down),
+            '<' => Some(Direction::Left),
+            _ => None,
+        }
+    }
+
+    pub fn to_arrow(self) -> char {
+        match self {
+            Direction::Up => '^',
+            Direction::Right => '>',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+        }
+    }
+}
+
+/// One of the four orthogonal and four diagonal directions, listed clockwise from `Up`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    pub const ALL: [Direction8; 8] = [
+        Direction8::Up,
+        Direction8::UpRight,
+        Direction8::Right,
+        Direction8::DownRight,
+        Direction8::Down,
+        Direction8::DownLeft,
+        Direction8::Left,
+        Direction8::UpLeft,
+    ];
+
+    pub fn vec(self) -> Vec2 {
+        match self {
+            Direction8::Up => Vec2::new(-1, 0),
+            Direction8::UpRight => Vec2::new(-1, 1),
+            Direction8::Right => Vec2::new(0, 1),
+            Direction8::DownRight => Vec2::new(1, 1),
+            Direction8::Down => Vec2::new(1, 0),
+            Direction8::DownLeft => Vec2::new(1, -1),
+            Direction8::Left => Vec2::new(0, -1),
+            Direction8::UpLeft => Vec2::new(-1, -1),
+        }
+    }
+
+    /// Turns clockwise by `eighth_turns`; negative values turn counter-clockwise.
+    pub fn rotate(self, eighth_turns: isize) -> Direction8 {
+        Direction8::ALL[(self as isize + eighth_turns).rem_euclid(8) as usize]
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(dir: Direction) -> Direction8 {
+        Direction8::ALL[dir as usize * 2]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic_test() {
+        let a = Pos::new(2, 3);
+        let b = Pos::new(5, -1);
+
+        assert_eq!(b - a, Vec2::new(3, -4));
+        assert_eq!(a + (b - a), b);
+        assert_eq!(a - (b - a) * 2, Pos::new(-4, 11));
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(Pos::from((4, 7)).to_string(), "7,4");
+        assert_eq!(Pos::new(-1, 0).to_idx(), None);
+    }
+
+    #[test]
+    fn bounds_test() {
+        let pos = Pos::new(0, 2);
+        assert_eq!(
+            pos.checked_offset(Vec2::new(1, 0), 3, 2),
+            Some(Pos::new(1, 2))
+        );
+        assert_eq!(pos.checked_offset(Vec2::new(0, 1), 3, 2), None);
+        assert_eq!(pos.checked_offset(Vec2::new(-1, 0), 3, 2), None);
+    }
+
+    #[test]
+    fn rotation_test() {
+        for dir in Direction::ALL {
+            assert_eq!(dir.vec().rotate_right(), dir.turn_right().vec());
+            assert_eq!(dir.vec().rotate_left(), dir.turn_left().vec());
+            assert_eq!(-dir.vec(), dir.reverse().vec());
+            assert_eq!(Direction8::from(dir).vec(), dir.vec());
+        }
+
+        assert_eq!(Direction::Left.rotate(-3), Direction::Up);
+        assert_eq!(Direction::Left.turns_to(Direction::Up), 1);
+        assert_eq!(Direction::Up.turns_to(Direction::Left), 3);
+        assert_eq!(Direction8::UpLeft.rotate(1), Direction8::Up);
+    }
+}
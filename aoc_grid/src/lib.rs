@@ -0,0 +1,440 @@
+pub mod geometry;
+
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+use aoc_common::ParseError;
+
+pub use geometry::{Direction, Direction8, Pos, Vec2};
+
+/// Grid index as `(row, column)`.
+pub type Idx = (usize, usize);
+
+const NEIGHBOURS_4: [Direction; 4] = [
+    Direction::Up,
+    Direction::Down,
+    Direction::Left,
+    Direction::Right,
+];
+
+/// Rectangular grid stored row by row in a single `Vec`.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "SerdeGrid<T>")
+)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+/// A [`Grid`] as it is read, before its size is checked.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct SerdeGrid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+#[cfg(feature = "serde")]
+impl<T> TryFrom<SerdeGrid<T>> for Grid<T> {
+    type Error = String;
+
+    fn try_from(grid: SerdeGrid<T>) -> Result<Grid<T>, String> {
+        let size = grid
+            .width
+            .checked_mul(grid.height)
+            .ok_or_else(|| format!("a {}x{} grid is too large", grid.width, grid.height))?;
+        if grid.cells.len() != size {
+            return Err(format!(
+                "{} cells do not fill a {}x{} grid",
+                grid.cells.len(),
+                grid.width,
+                grid.height
+            ));
+        }
+        Ok(Grid::from_vec(grid.width, grid.height, grid.cells))
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    pub fn from_vec(width: usize, height: usize, cells: Vec<T>) -> Grid<T> {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "cell count does not match grid size"
+        );
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parses one row per line, mapping each character to a cell with `f`.
+    /// Surrounding whitespace of the input and of every line is ignored.
+    pub fn parse(input: &str, mut f: impl FnMut(char) -> T) -> Result<Grid<T>, ParseError> {
+        Grid::try_parse(input, "", |chr| Some(f(chr)))
+    }
+
+    /// Like [`Grid::parse`], but `f` may reject a character, which is reported
+    /// as a parse error saying `expected`.
+    pub fn try_parse(
+        input: &str,
+        expected: &str,
+        mut f: impl FnMut(char) -> Option<T>,
+    ) -> Result<Grid<T>, ParseError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::with_capacity(input.len());
+
+        for line in input.trim().lines().map(str::trim) {
+            let row_len = line.chars().count();
+
+            match width {
+                None => width = Some(row_len),
+                Some(width) if width != row_len => {
+                    let column = line
+                        .char_indices()
+                        .nth(width)
+                        .map_or(line.len(), |(idx, _)| idx);
+                    return Err(ParseError::at(
+                        input,
+                        &line[column..],
+                        format!("a row of {width} cells"),
+                    ));
+                }
+                Some(_) => {}
+            }
+
+            for (idx, chr) in line.char_indices() {
+                let cell = f(chr).ok_or_else(|| ParseError::at(input, &line[idx..], expected))?;
+                cells.push(cell);
+            }
+            height += 1;
+        }
+
+        Ok(Grid::from_vec(width.unwrap_or(0), height, cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, idx: Idx) -> bool {
+        idx.0 < self.height && idx.1 < self.width
+    }
+
+    pub fn get(&self, idx: Idx) -> Option<&T> {
+        if self.contains(idx) {
+            Some(&self.cells[idx.0 * self.width + idx.1])
+        } else {
+            None
+        }
+    }
+
+    pub fn contains_pos(&self, pos: Pos) -> bool {
+        pos.in_bounds(self.width, self.height)
+    }
+
+    pub fn get_pos(&self, pos: Pos) -> Option<&T> {
+        self.get(pos.to_idx()?)
+    }
+
+    pub fn get_mut(&mut self, idx: Idx) -> Option<&mut T> {
+        if self.contains(idx) {
+            Some(&mut self.cells[idx.0 * self.width + idx.1])
+        } else {
+            None
+        }
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// All indices in row-major order.
+    pub fn indices(&self) -> impl Iterator<Item = Idx> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| (row, col)))
+    }
+
+    /// All cells with their indices in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Idx, &T)> {
+        self.indices().zip(self.cells.iter())
+    }
+
+    /// Moves `idx` by `delta`, or `None` if that leaves the grid.
+    pub fn offset(&self, idx: Idx, delta: Vec2) -> Option<Idx> {
+        Pos::from(idx)
+            .checked_offset(delta, self.width, self.height)?
+            .to_idx()
+    }
+
+    /// Orthogonal neighbours inside the grid, in the order up, down, left, right.
+    pub fn neighbours4(&self, idx: Idx) -> impl Iterator<Item = Idx> + '_ {
+        NEIGHBOURS_4
+            .iter()
+            .filter_map(move |dir| self.offset(idx, dir.vec()))
+    }
+
+    /// Orthogonal and diagonal neighbours inside the grid, clockwise from up.
+    pub fn neighbours8(&self, idx: Idx) -> impl Iterator<Item = Idx> + '_ {
+        Direction8::ALL
+            .iter()
+            .filter_map(move |dir| self.offset(idx, dir.vec()))
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|row| self.row(row))
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(col).step_by(self.width.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|col| self.column(col))
+    }
+
+    /// Cells from `start` going down and to the right.
+    pub fn diagonal(&self, start: Idx) -> impl Iterator<Item = &T> {
+        (0..)
+            .map(move |i| (start.0 + i, start.1 + i))
+            .map_while(|idx| self.get(idx))
+    }
+
+    /// Cells from `start` going down and to the left.
+    pub fn anti_diagonal(&self, start: Idx) -> impl Iterator<Item = &T> {
+        (0..)
+            .map_while(move |i| Some((start.0 + i, start.1.checked_sub(i)?)))
+            .map_while(|idx| self.get(idx))
+    }
+
+    /// Every down-right diagonal: those starting in the top row from left to right,
+    /// then those starting in the left column from top to bottom.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let top_row = (0..self.width).map(|col| (0, col));
+        let left_column = (1..self.height).map(|row| (row, 0));
+        top_row.chain(left_column).map(|start| self.diagonal(start))
+    }
+
+    /// Every down-left diagonal: those starting in the top row from left to right,
+    /// then those starting in the right column from top to bottom.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let top_row = (0..self.width).map(|col| (0, col));
+        let right_column = (1..self.height).map(|row| (row, self.width.saturating_sub(1)));
+        top_row
+            .chain(right_column)
+            .map(|start| self.anti_diagonal(start))
+    }
+
+    /// Index of the first cell equal to `value`, in row-major order.
+    pub fn find(&self, value: &T) -> Option<Idx>
+    where
+        T: PartialEq,
+    {
+        self.positions_of(value).next()
+    }
+
+    /// Indices of every cell equal to `value`, in row-major order.
+    pub fn positions_of<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Idx> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter(move |(_, cell)| *cell == value)
+            .map(|(idx, _)| idx)
+    }
+}
+
+impl<T> Index<Idx> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, idx: Idx) -> &T {
+        match self.get(idx) {
+            Some(cell) => cell,
+            None => panic!(
+                "index {idx:?} out of bounds for {}x{} grid",
+                self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<Idx> for Grid<T> {
+    fn index_mut(&mut self, idx: Idx) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match self.get_mut(idx) {
+            Some(cell) => cell,
+            None => panic!("index {idx:?} out of bounds for {width}x{height} grid"),
+        }
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        match self.get_pos(pos) {
+            Some(cell) => cell,
+            None => panic!(
+                "position {pos:?} out of bounds for {}x{} grid",
+                self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match pos.to_idx().and_then(|idx| self.get_mut(idx)) {
+            Some(cell) => cell,
+            None => panic!("position {pos:?} out of bounds for {width}x{height} grid"),
+        }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (row_idx, row) in self.rows().enumerate() {
+            if row_idx > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Grid<char> {
+        Grid::parse("abcd\nefgh\nijkl", |c| c).unwrap()
+    }
+
+    fn collect<'a>(iter: impl Iterator<Item = impl Iterator<Item = &'a char>>) -> Vec<String> {
+        iter.map(|line| line.collect()).collect()
+    }
+
+    #[test]
+    fn parse_test() {
+        let grid = sample();
+        assert_eq!(grid.width(), 4);
+        assert_eq!(grid.height(), 3);
+        assert_eq!(grid[(1, 2)], 'g');
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get((0, 4)), None);
+
+        let grid = Grid::parse("\n    12\n    34\n", |c| c.to_digit(10).unwrap()).unwrap();
+        assert_eq!(grid, Grid::from_vec(2, 2, vec![1, 2, 3, 4]));
+
+        let err = Grid::parse("abc\nab", |c| c).unwrap_err();
+        assert_eq!((err.line(), err.column()), (2, 3));
+
+        let err = Grid::try_parse("12\n3x\n", "a digit", |c| c.to_digit(10)).unwrap_err();
+        assert_eq!((err.line(), err.column()), (2, 2));
+        assert_eq!(err.expected(), "a digit");
+    }
+
+    #[test]
+    fn neighbours_test() {
+        let grid = sample();
+
+        let corner: Vec<Idx> = grid.neighbours4((0, 0)).collect();
+        assert_eq!(corner, vec![(1, 0), (0, 1)]);
+
+        let middle: Vec<Idx> = grid.neighbours4((1, 1)).collect();
+        assert_eq!(middle, vec![(0, 1), (2, 1), (1, 0), (1, 2)]);
+
+        assert_eq!(grid.neighbours8((0, 0)).count(), 3);
+        assert_eq!(grid.neighbours8((1, 1)).count(), 8);
+        assert_eq!(grid.neighbours8((2, 3)).count(), 3);
+    }
+
+    #[test]
+    fn lines_test() {
+        let grid = sample();
+
+        assert_eq!(grid.row(1), ['e', 'f', 'g', 'h']);
+        assert_eq!(collect(grid.columns()), vec!["aei", "bfj", "cgk", "dhl"]);
+        assert_eq!(
+            collect(grid.diagonals()),
+            vec!["afk", "bgl", "ch", "d", "ej", "i"]
+        );
+        assert_eq!(
+            collect(grid.anti_diagonals()),
+            vec!["a", "be", "cfi", "dgj", "hk", "l"]
+        );
+    }
+
+    #[test]
+    fn find_test() {
+        let grid = Grid::parse("#.#\n.#.", |c| c).unwrap();
+        assert_eq!(grid.find(&'#'), Some((0, 0)));
+        assert_eq!(grid.find(&'x'), None);
+
+        let walls: Vec<Idx> = grid.positions_of(&'#').collect();
+        assert_eq!(walls, vec![(0, 0), (0, 2), (1, 1)]);
+    }
+
+    #[test]
+    fn display_test() {
+        let mut grid = sample();
+        grid[(0, 0)] = '#';
+        assert_eq!(grid.to_string(), "#bcd\nefgh\nijkl");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_test() {
+        let grid = Grid::parse("ab\ncd", |c| c).unwrap();
+        let json = serde_json::to_string(&grid).unwrap();
+        assert_eq!(json, r#"{"width":2,"height":2,"cells":["a","b","c","d"]}"#);
+        assert_eq!(serde_json::from_str::<Grid<char>>(&json).unwrap(), grid);
+
+        let json = r#"{"width":2,"height":2,"cells":["a","b","c"]}"#;
+        let err = serde_json::from_str::<Grid<char>>(json).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("3 cells do not fill a 2x2 grid"));
+
+        let json = format!(r#"{{"width":{},"height":2,"cells":[]}}"#, usize::MAX);
+        let err = serde_json::from_str::<Grid<char>>(&json).unwrap_err();
+        let expected = format!("a {}x2 grid is too large", usize::MAX);
+        assert!(err.to_string().starts_with(&expected));
+    }
+}
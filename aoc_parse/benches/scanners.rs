@@ -0,0 +1,90 @@
+//! The scanners against the regular expressions they replaced, on the
+//! generated robots of day 14 and claw machines of day 13.
+//!
+//! The regular expressions are compiled once, outside the measured loop, so
+//! this is the gain on top of no longer compiling one per line. The whole
+//! parsers are measured by `cargo bench -p aoc -- "day1[34]/generated/parse"`.
+
+use std::hint::black_box;
+
+use aoc_gen::Rng;
+use criterion::{criterion_group, criterion_main, Criterion};
+use regex::Regex;
+
+fn robots(c: &mut Criterion) {
+    let input = aoc_gen::day14::input(&mut Rng::new(14), aoc_gen::day14::PUZZLE_SIZE);
+    let re = Regex::new(r"^p=(\d+),(\d+) v=(-?\d+),(-?\d+)$").unwrap();
+
+    let mut group = c.benchmark_group("robots");
+    group.bench_function("regex", |b| {
+        b.iter(|| {
+            black_box(&input)
+                .lines()
+                .map(|line| {
+                    let captures = re.captures(line).unwrap();
+                    let field = |idx: usize| captures[idx].parse::<i64>().unwrap();
+                    [field(1), field(2), field(3), field(4)]
+                })
+                .collect::<Vec<_>>()
+        })
+    });
+    group.bench_function("ints", |b| {
+        b.iter(|| {
+            black_box(&input)
+                .lines()
+                .map(|line| aoc_parse::ints_exact::<i64, 4>(line).unwrap())
+                .collect::<Vec<_>>()
+        })
+    });
+    group.finish();
+}
+
+fn machines(c: &mut Criterion) {
+    let input = aoc_gen::day13::input(&mut Rng::new(13), aoc_gen::day13::PUZZLE_SIZE);
+    let button = Regex::new(r"^Button [AB]: X\+(\d+), Y\+(\d+)$").unwrap();
+    let prize = Regex::new(r"^Prize: X=(\d+), Y=(\d+)$").unwrap();
+
+    let mut group = c.benchmark_group("machines");
+    group.bench_function("regex", |b| {
+        b.iter(|| {
+            black_box(&input)
+                .split("\n\n")
+                .map(|section| {
+                    let mut numbers = [0u64; 6];
+                    for (idx, line) in section.lines().enumerate() {
+                        let re = if idx < 2 { &button } else { &prize };
+                        let captures = re.captures(line).unwrap();
+                        numbers[2 * idx] = captures[1].parse().unwrap();
+                        numbers[2 * idx + 1] = captures[2].parse().unwrap();
+                    }
+                    numbers
+                })
+                .collect::<Vec<_>>()
+        })
+    });
+    group.bench_function("scanners", |b| {
+        b.iter(|| {
+            aoc_parse::split_sections(black_box(&input))
+                .map(|section| {
+                    let mut numbers = [0u64; 6];
+                    for (idx, line) in section.lines().enumerate() {
+                        let [x, y] = match idx {
+                            0 | 1 => aoc_parse::ints_exact(line).unwrap(),
+                            _ => [
+                                aoc_parse::key_value(line, "X").unwrap(),
+                                aoc_parse::key_value(line, "Y").unwrap(),
+                            ],
+                        };
+                        numbers[2 * idx] = x;
+                        numbers[2 * idx + 1] = y;
+                    }
+                    numbers
+                })
+                .collect::<Vec<_>>()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, robots, machines);
+criterion_main!(benches);
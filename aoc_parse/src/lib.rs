@@ -0,0 +1,214 @@
+//! Scanners for the usual shapes of puzzle input: numbers scattered through
+//! a line, `key=value` fields and blocks separated by blank lines.
+//!
+//! None of them allocate. Errors are located relative to the text they were
+//! given; use [`ParseError::within`] to move them into a larger input.
+
+use std::{marker::PhantomData, str::FromStr};
+
+use aoc_common::{parse_number, ParseError};
+
+/// Every integer in `text`, in order. A `-` right before the digits makes the
+/// number negative; anything else between numbers is skipped.
+///
+/// ```
+/// let numbers: Vec<i64> = aoc_parse::ints("p=0,4 v=3,-3").map(Result::unwrap).collect();
+/// assert_eq!(numbers, [0, 4, 3, -3]);
+/// ```
+pub fn ints<T: FromStr>(text: &str) -> Ints<'_, T> {
+    Ints {
+        text,
+        pos: 0,
+        number: PhantomData,
+    }
+}
+
+/// Iterator returned by [`ints`].
+pub struct Ints<'a, T> {
+    text: &'a str,
+    pos: usize,
+    number: PhantomData<fn() -> T>,
+}
+
+impl<'a, T> Ints<'a, T> {
+    /// The next integer as it is written, sign included.
+    fn next_token(&mut self) -> Option<&'a str> {
+        let bytes = self.text.as_bytes();
+        let digits = self.pos + bytes[self.pos..].iter().position(u8::is_ascii_digit)?;
+        let end = digits
+            + bytes[digits..]
+                .iter()
+                .position(|byte| !byte.is_ascii_digit())
+                .unwrap_or(bytes.len() - digits);
+
+        let is_negative = digits > self.pos && bytes[digits - 1] == b'-';
+        let start = if is_negative { digits - 1 } else { digits };
+        self.pos = end;
+        Some(&self.text[start..end])
+    }
+}
+
+impl<T: FromStr> Iterator for Ints<'_, T> {
+    type Item = Result<T, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let token = self.next_token()?;
+        Some(parse_number(self.text, token))
+    }
+}
+
+/// Exactly `N` integers from `text`, as found by [`ints`].
+pub fn ints_exact<T: FromStr, const N: usize>(text: &str) -> Result<[T; N], ParseError> {
+    let mut ints = ints::<T>(text);
+    let mut values: [Option<T>; N] = std::array::from_fn(|_| None);
+
+    for value in values.iter_mut() {
+        match ints.next() {
+            Some(number) => *value = Some(number?),
+            None => return Err(ParseError::at_end(text, format!("{N} numbers"))),
+        }
+    }
+    if let Some(extra) = ints.next_token() {
+        return Err(ParseError::at(text, extra, format!("only {N} numbers")));
+    }
+
+    Ok(values.map(|value| value.unwrap()))
+}
+
+/// The raw value of the `key=value` field in `text`. It runs up to the next
+/// whitespace, without a trailing `,` or `;`.
+///
+/// ```
+/// assert_eq!(aoc_parse::value("p=0,4 v=3,-3", "v"), Ok("3,-3"));
+/// assert_eq!(aoc_parse::value("Prize: X=8400, Y=5400", "X"), Ok("8400"));
+/// ```
+pub fn value<'a>(text: &'a str, key: &str) -> Result<&'a str, ParseError> {
+    let is_key_char = |chr: char| chr.is_alphanumeric() || chr == '_';
+
+    let start = text
+        .match_indices(key)
+        .filter(|(idx, _)| !text[..*idx].ends_with(is_key_char))
+        .map(|(idx, _)| idx + key.len())
+        .find(|end| text[*end..].starts_with('='))
+        .map(|end| end + 1)
+        .ok_or_else(|| ParseError::at(text, text, format!("a `{key}=` field")))?;
+
+    let rest = &text[start..];
+    let value = &rest[..rest.find(char::is_whitespace).unwrap_or(rest.len())];
+    let value = value.trim_end_matches([',', ';']);
+    if value.is_empty() {
+        return Err(ParseError::at(text, value, format!("a value for `{key}`")));
+    }
+    Ok(value)
+}
+
+/// The value of the `key=value` field in `text`, see [`value`], parsed as a `T`.
+pub fn key_value<T: FromStr>(text: &str, key: &str) -> Result<T, ParseError> {
+    let value = value(text, key)?;
+    parse_number(text, value)
+}
+
+/// The blocks of lines in `input` that are separated by blank lines, with
+/// either `\n` or `\r\n` line ends. Each block is a slice of `input` from
+/// the start of its first line to the end of its last line, line end
+/// excluded.
+///
+/// ```
+/// let sections: Vec<&str> = aoc_parse::split_sections("a\r\nb\r\n\r\n\r\nc\r\n").collect();
+/// assert_eq!(sections, ["a\r\nb", "c"]);
+/// ```
+pub fn split_sections(input: &str) -> Sections<'_> {
+    Sections { input, pos: 0 }
+}
+
+/// Iterator returned by [`split_sections`].
+pub struct Sections<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Iterator for Sections<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        let mut start = None;
+        let mut end = self.pos;
+
+        for line in self.input[self.pos..].split_inclusive('\n') {
+            let line_start = self.pos;
+            self.pos += line.len();
+
+            let content = line.trim_end_matches(['\n', '\r']);
+            if content.trim().is_empty() {
+                if start.is_some() {
+                    break;
+                }
+                continue;
+            }
+            start.get_or_insert(line_start);
+            end = line_start + content.len();
+        }
+
+        start.map(|start| &self.input[start..end])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ints_test() {
+        let numbers: Vec<i64> = ints("Button A: X+94, Y-34 -x 7-2")
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(numbers, [94, -34, 7, -2]);
+        assert_eq!(ints::<u8>("no numbers - here").count(), 0);
+
+        let err = ints::<u8>("1 2 300").nth(2).unwrap().unwrap_err();
+        assert_eq!((err.line(), err.column()), (1, 5));
+        let err = ints::<u64>("x=-1").next().unwrap().unwrap_err();
+        assert_eq!((err.line(), err.column()), (1, 3));
+    }
+
+    #[test]
+    fn ints_exact_test() {
+        assert_eq!(ints_exact::<i32, 4>("p=0,4 v=3,-3"), Ok([0, 4, 3, -3]));
+
+        let err = ints_exact::<i32, 2>("X+94").unwrap_err();
+        assert_eq!((err.column(), err.expected()), (5, "2 numbers"));
+        let err = ints_exact::<i32, 2>("X+94, Y+34, Z+1").unwrap_err();
+        assert_eq!((err.column(), err.expected()), (15, "only 2 numbers"));
+    }
+
+    #[test]
+    fn value_test() {
+        let line = "max=3 x=-1, y=2;";
+        assert_eq!(value(line, "x"), Ok("-1"));
+        assert_eq!(key_value::<i32>(line, "y"), Ok(2));
+        assert_eq!(key_value::<u32>(line, "max"), Ok(3));
+
+        let err = value(line, "z").unwrap_err();
+        assert_eq!((err.column(), err.expected()), (1, "a `z=` field"));
+        let err = value("a= b", "a").unwrap_err();
+        assert_eq!((err.column(), err.expected()), (3, "a value for `a`"));
+        let err = key_value::<u32>(line, "x").unwrap_err();
+        assert_eq!((err.column(), err.expected()), (9, "a number"));
+    }
+
+    #[test]
+    fn split_sections_test() {
+        let sections: Vec<&str> = split_sections("\n\na\nb\n\nc\n  \n\nd").collect();
+        assert_eq!(sections, ["a\nb", "c", "d"]);
+
+        let input = "1|2\r\n3|4\r\n\r\n5,6\r\n";
+        let sections: Vec<&str> = split_sections(input).collect();
+        assert_eq!(sections, ["1|2\r\n3|4", "5,6"]);
+        // the sections point into the input, for `ParseError::within`
+        let err = ParseError::at(sections[1], sections[1], "").within(input, sections[1]);
+        assert_eq!((err.line(), err.column()), (4, 1));
+
+        assert_eq!(split_sections("").count(), 0);
+        assert_eq!(split_sections("\r\n \n").count(), 0);
+    }
+}
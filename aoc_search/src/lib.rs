@@ -0,0 +1,387 @@
+//! Shortest path searches over implicit graphs.
+//!
+//! States only need to be `Clone + Eq + Hash`; the graph is described by a
+//! successor function returning `(next_state, step_cost)` pairs.
+
+use std::{
+    cmp::Ordering,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+/// Path cost: anything that can be summed, compared and starts at `Default::default()`.
+pub trait Cost: Copy + Ord + Add<Output = Self> + Default {}
+
+impl<T: Copy + Ord + Add<Output = T> + Default> Cost for T {}
+
+/// A cheapest path, from the start state to a goal state, both included.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Path<S, C> {
+    pub states: Vec<S>,
+    pub cost: C,
+}
+
+/// Every cheapest path at once, stored as the best predecessors of each state.
+#[derive(Clone, Debug)]
+pub struct BestPaths<S, C> {
+    pub cost: C,
+    pub start: S,
+    /// Goal states reached at `cost`.
+    pub goals: Vec<S>,
+    predecessors: HashMap<S, Vec<S>>,
+}
+
+impl<S: Clone + Eq + Hash, C: Cost> BestPaths<S, C> {
+    /// The predecessors through which `state` is reached at its best cost.
+    pub fn predecessors(&self, state: &S) -> &[S] {
+        self.predecessors.get(state).map_or(&[], Vec::as_slice)
+    }
+
+    /// All states lying on at least one cheapest path.
+    pub fn states(&self) -> HashSet<S> {
+        let mut seen: HashSet<S> = self.goals.iter().cloned().collect();
+        let mut stack = self.goals.clone();
+
+        while let Some(state) = stack.pop() {
+            for predecessor in self.predecessors(&state) {
+                if seen.insert(predecessor.clone()) {
+                    stack.push(predecessor.clone());
+                }
+            }
+        }
+
+        seen
+    }
+
+    /// One of the cheapest paths.
+    pub fn path(&self) -> Path<S, C> {
+        let mut states = vec![self.goals[0].clone()];
+        while *states.last().unwrap() != self.start {
+            let predecessor = &self.predecessors(states.last().unwrap())[0];
+            states.push(predecessor.clone());
+        }
+        states.reverse();
+
+        Path {
+            states,
+            cost: self.cost,
+        }
+    }
+}
+
+/// Breadth-first search where every step costs 1.
+pub fn bfs<S, I>(
+    start: S,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, usize>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut parents = HashMap::<S, Option<S>>::from([(start.clone(), None)]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(state) = queue.pop_front() {
+        if is_goal(&state) {
+            let mut states = vec![state];
+            while let Some(Some(parent)) = parents.get(states.last().unwrap()) {
+                states.push(parent.clone());
+            }
+            states.reverse();
+
+            let cost = states.len() - 1;
+            return Some(Path { states, cost });
+        }
+
+        for next in neighbours(&state) {
+            if let Entry::Vacant(entry) = parents.entry(next.clone()) {
+                entry.insert(Some(state.clone()));
+                queue.push_back(next);
+            }
+        }
+    }
+
+    None
+}
+
+/// Dijkstra's algorithm; step costs must not be negative.
+pub fn dijkstra<S, C, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(start, successors, |_| C::default(), is_goal)
+}
+
+/// Like [`dijkstra`], but keeps going until every cheapest path is known.
+/// Every step must cost more than zero.
+pub fn dijkstra_all<S, C, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<BestPaths<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar_all(start, successors, |_| C::default(), is_goal)
+}
+
+/// A* search; `heuristic` must never overestimate the remaining cost.
+pub fn astar<S, C, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    heuristic: impl FnMut(&S) -> C,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+{
+    search(start, successors, heuristic, is_goal, false).map(|best| best.path())
+}
+
+/// Like [`astar`], but keeps going until every cheapest path is known. The
+/// heuristic must also be consistent, or some equal-cost paths may be missed,
+/// and every step must cost more than zero.
+pub fn astar_all<S, C, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    heuristic: impl FnMut(&S) -> C,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<BestPaths<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+{
+    search(start, successors, heuristic, is_goal, true)
+}
+
+/// Open list entry, ordered so that `BinaryHeap` pops the lowest estimate first.
+struct Candidate<S, C> {
+    estimate: C,
+    cost: C,
+    state: S,
+}
+
+impl<S, C: Ord> Ord for Candidate<S, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .estimate
+            .cmp(&self.estimate)
+            // on ties, prefer the candidate that got further already
+            .then(self.cost.cmp(&other.cost))
+    }
+}
+
+impl<S, C: Ord> PartialOrd for Candidate<S, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S, C: Ord> PartialEq for Candidate<S, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<S, C: Ord> Eq for Candidate<S, C> {}
+
+fn search<S, C, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+    find_all: bool,
+) -> Option<BestPaths<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+{
+    // best known cost of every discovered state, with the predecessors reaching it at that cost
+    let mut best = HashMap::<S, (C, Vec<S>)>::new();
+    let mut open = BinaryHeap::new();
+    let mut goals = Vec::new();
+    let mut goal_cost = None;
+
+    best.insert(start.clone(), (C::default(), vec![]));
+    open.push(Candidate {
+        estimate: heuristic(&start),
+        cost: C::default(),
+        state: start.clone(),
+    });
+
+    while let Some(Candidate {
+        estimate,
+        cost,
+        state,
+    }) = open.pop()
+    {
+        if goal_cost.is_some_and(|goal_cost| estimate > goal_cost) {
+            break;
+        }
+        if best[&state].0 < cost {
+            // a cheaper way to this state was found after this candidate was queued
+            continue;
+        }
+
+        if is_goal(&state) {
+            goal_cost = Some(cost);
+            goals.push(state);
+            if !find_all {
+                break;
+            }
+            continue;
+        }
+
+        for (next, step_cost) in successors(&state) {
+            // equal-cost predecessors joined by a free step would point at each other
+            assert!(
+                !find_all || step_cost > C::default(),
+                "searches for every cheapest path need steps that cost something"
+            );
+            let next_cost = cost + step_cost;
+
+            match best.entry(next.clone()) {
+                Entry::Vacant(entry) => {
+                    entry.insert((next_cost, vec![state.clone()]));
+                }
+                Entry::Occupied(mut entry) => {
+                    let (known_cost, predecessors) = entry.get_mut();
+                    if next_cost < *known_cost {
+                        *known_cost = next_cost;
+                        *predecessors = vec![state.clone()];
+                    } else {
+                        if next_cost == *known_cost && find_all {
+                            predecessors.push(state.clone());
+                        }
+                        continue;
+                    }
+                }
+            }
+
+            open.push(Candidate {
+                estimate: next_cost + heuristic(&next),
+                cost: next_cost,
+                state: next,
+            });
+        }
+    }
+
+    Some(BestPaths {
+        cost: goal_cost?,
+        start,
+        goals,
+        predecessors: best
+            .into_iter()
+            .map(|(state, (_, predecessors))| (state, predecessors))
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Weighted graph as adjacency lists of `(to, cost)`.
+    fn graph() -> HashMap<u8, Vec<(u8, u32)>> {
+        HashMap::from([
+            (0, vec![(1, 1), (2, 4)]),
+            (1, vec![(2, 2), (3, 6)]),
+            (2, vec![(3, 3)]),
+            (3, vec![]),
+            (4, vec![(0, 1)]),
+        ])
+    }
+
+    #[test]
+    fn dijkstra_test() {
+        let graph = graph();
+        let successors = |node: &u8| graph[node].clone();
+
+        let path = dijkstra(0, successors, |node| *node == 3).unwrap();
+        assert_eq!(path.states, vec![0, 1, 2, 3]);
+        assert_eq!(path.cost, 6);
+
+        assert_eq!(dijkstra(0, successors, |node| *node == 4), None);
+        assert_eq!(dijkstra(3, successors, |node| *node == 3).unwrap().cost, 0);
+    }
+
+    #[test]
+    fn all_paths_test() {
+        // 0-1-3 and 0-2-3 both cost 5, 0-3 costs 6
+        let graph = HashMap::from([
+            (0, vec![(1, 2), (2, 1), (3, 6)]),
+            (1, vec![(3, 3)]),
+            (2, vec![(3, 4)]),
+            (3, vec![]),
+        ]);
+        let best = dijkstra_all(0, |node: &u8| graph[node].clone(), |node| *node == 3).unwrap();
+
+        assert_eq!(best.cost, 5);
+        assert_eq!(best.goals, vec![3]);
+        assert_eq!(best.states(), HashSet::from([0, 1, 2, 3]));
+
+        let mut predecessors = best.predecessors(&3).to_vec();
+        predecessors.sort();
+        assert_eq!(predecessors, vec![1, 2]);
+        assert_eq!(best.path().cost, 5);
+        assert_eq!(best.path().states.len(), 3);
+    }
+
+    #[test]
+    fn zero_cost_test() {
+        // 1 leads back to the start for free
+        let graph = HashMap::from([(0, vec![(1, 0)]), (1, vec![(0, 0), (2, 1)]), (2, vec![])]);
+        let successors = |node: &u8| graph[node].clone();
+
+        let path = dijkstra(0, successors, |node| *node == 2).unwrap();
+        assert_eq!(path.states, vec![0, 1, 2]);
+        assert_eq!(path.cost, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "steps that cost something")]
+    fn zero_cost_all_paths_test() {
+        let graph = HashMap::from([(0, vec![(1, 0)]), (1, vec![(0, 0), (2, 1)]), (2, vec![])]);
+        dijkstra_all(0, |node: &u8| graph[node].clone(), |node| *node == 2);
+    }
+
+    #[test]
+    fn grid_test() {
+        // 5x5 open grid with a wall at x == 2 for y < 4
+        let neighbours = |&(y, x): &(i32, i32)| {
+            [(y - 1, x), (y + 1, x), (y, x - 1), (y, x + 1)]
+                .into_iter()
+                .filter(|&(y, x)| (0..5).contains(&y) && (0..5).contains(&x) && !(x == 2 && y < 4))
+        };
+        let target = (0, 4);
+
+        let path = bfs((0, 0), neighbours, |pos| *pos == target).unwrap();
+        assert_eq!(path.cost, 12);
+        assert_eq!(path.states.len(), 13);
+
+        let path = astar(
+            (0, 0),
+            |pos| neighbours(pos).map(|next| (next, 1)),
+            |&(y, x)| y.abs_diff(target.0) + x.abs_diff(target.1),
+            |pos| *pos == target,
+        )
+        .unwrap();
+        assert_eq!(path.cost, 12);
+        assert_eq!(path.states.first(), Some(&(0, 0)));
+        assert_eq!(path.states.last(), Some(&target));
+    }
+}
@@ -1,22 +1,27 @@
 use std::iter::zip;
 use std::collections::HashMap;
 
-pub fn parse_input(input: String) -> (Vec<i64>, Vec<i64>){
+use aoc_common::{parse_number, ParseError, Solution, SolveError};
 
-    let int_pairs = input.trim().split('\n')
-        .map(|str| str.trim())
-        .map(|str| str.split_once(char::is_whitespace).unwrap())
-        .map(|pair| (pair.0.trim().parse::<i64>().unwrap(), pair.1.trim().parse::<i64>().unwrap()));
+pub fn parse_input(input: &str) -> Result<(Vec<i64>, Vec<i64>), ParseError>{
 
     let mut left: Vec<i64> = Vec::new();
     let mut right: Vec<i64> = Vec::new();
 
-    for pair in int_pairs{
-        left.push(pair.0);
-        right.push(pair.1);
+    for line in input.lines().filter(|line| !line.trim().is_empty()){
+        let mut numbers = line.split_whitespace();
+        let (Some(first), Some(second)) = (numbers.next(), numbers.next()) else {
+            return Err(ParseError::at(input, &line[line.len()..], "two numbers separated by whitespace"));
+        };
+        if let Some(extra) = numbers.next() {
+            return Err(ParseError::at(input, extra, "end of line"));
+        }
+
+        left.push(parse_number(input, first)?);
+        right.push(parse_number(input, second)?);
     }
 
-    (left, right)
+    Ok((left, right))
 }
 
 pub fn calc_dist(left: &Vec<i64>, right: &Vec<i64>) -> i64{
@@ -44,4 +49,28 @@ pub fn calc_similarity_score(left: &Vec<i64>, right: &Vec<i64>) -> i64{
     left.iter()
         .map(|number| counts.get(number).unwrap_or(&0) * number)
         .sum()
-}
\ No newline at end of file
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = (Vec<i64>, Vec<i64>);
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1((left, right): &Self::Input) -> Result<i64, SolveError> {
+        let mut left = left.clone();
+        let mut right = right.clone();
+        left.sort();
+        right.sort();
+        Ok(calc_dist(&left, &right))
+    }
+
+    fn part2((left, right): &Self::Input) -> Result<i64, SolveError> {
+        Ok(calc_similarity_score(left, right))
+    }
+}
@@ -1,17 +1,7 @@
-use std::fs;
+use std::process::ExitCode;
 
-use day01::*;
+use day01::Day01;
 
-fn main() {
-    let input = fs::read_to_string("input/input.txt").unwrap();
-    let (mut left, mut right) = parse_input(input);
-
-    left.sort();
-    right.sort();
-
-    let dist = calc_dist(&left, &right);
-    println!("Dist: {dist}");
-
-    let similarity_score = calc_similarity_score(&left, &right);
-    println!("Similariy score: {similarity_score}");
+fn main() -> ExitCode {
+    aoc_common::run_main::<Day01>(1)
 }
@@ -1,14 +1,16 @@
-type Level = i64;
-type Report = Vec<Level>;
+use aoc_common::{parse_number, ParseError, Solution, SolveError};
 
+pub type Level = i64;
+pub type Report = Vec<Level>;
 
-pub fn parse_input(input: &String) -> Vec<Vec<i64>> {
+
+pub fn parse_input(input: &str) -> Result<Vec<Report>, ParseError> {
     input
-        .split("\n")
-        .filter(|line| !line.is_empty())
+        .lines()
+        .filter(|line| !line.trim().is_empty())
         .map(|line| {
-            line.split(char::is_whitespace)
-                .map(|str| str.parse::<i64>().unwrap())
+            line.split_whitespace()
+                .map(|str| parse_number(input, str))
                 .collect()
         })
         .collect()
@@ -45,8 +47,28 @@ pub fn is_safe_with_dampener(report: &Report) -> bool {
     return false;
 }
 
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<Report>;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
 
+    fn part1(report_list: &Self::Input) -> Result<usize, SolveError> {
+        Ok(report_list.iter().filter(|report| is_safe(report)).count())
+    }
+
+    fn part2(report_list: &Self::Input) -> Result<usize, SolveError> {
+        Ok(report_list
+            .iter()
+            .filter(|report| is_safe_with_dampener(report))
+            .count())
+    }
+}
 
 #[cfg(test)]
 mod tests{
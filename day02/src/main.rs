@@ -1,13 +1,7 @@
-use std::fs;
-use day02::*;
+use std::process::ExitCode;
 
-fn main() {
-    let input = fs::read_to_string("input/input.txt").unwrap();
-    let report_list = parse_input(&input);
+use day02::Day02;
 
-    let safe_count = report_list.iter().filter(|report| is_safe(report)).count();
-    println!("Safe reports count: {safe_count}");
-
-    let safe_with_dampener_count = report_list.iter().filter(|report| is_safe_with_dampener(&report)).count();
-    println!("Safe reports with dampener count: {safe_with_dampener_count}");
+fn main() -> ExitCode {
+    aoc_common::run_main::<Day02>(2)
 }
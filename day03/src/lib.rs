@@ -1,33 +1,88 @@
-use regex::{Regex, RegexBuilder};
+use aoc_common::{checked_sum, ParseError, Solution, SolveError};
+use aoc_parse::ints_exact;
 
-pub fn parse_input(input: &str) -> Vec<(i64, i64)> {
+pub fn parse_input(input: &str) -> Result<Vec<(i64, i64)>, ParseError> {
 
-    let re = Regex::new(r"(?:mul\((\d+,\d+)\))").unwrap();
-
-    re.captures_iter(input)
-        .map(|captures| captures.get(1).unwrap().as_str())
-        .map(|str| str.split_once(",").unwrap())
-        .map(|str_pair| (str_pair.0.parse::<i64>().unwrap(), str_pair.1.parse::<i64>().unwrap()))
+    input.match_indices("mul(")
+        .filter_map(|(idx, call)| mul_arguments(&input[idx + call.len()..]))
+        .map(|arguments| {
+            let [x, y] = ints_exact(arguments).map_err(|err| err.within(input, arguments))?;
+            Ok((x, y))
+        })
         .collect()
 }
 
-pub fn calc_multiplications(pairs: &Vec<(i64, i64)>) -> i64 {
+/// The `<x>,<y>` of `mul(<x>,<y>)` if `rest` follows a well-formed `mul(`.
+fn mul_arguments(rest: &str) -> Option<&str> {
+    let end = rest.find(|chr: char| !chr.is_ascii_digit() && chr != ',')?;
+    let arguments = &rest[..end];
+    let (x, y) = arguments.split_once(',')?;
 
-    pairs.iter()
-        .map(|pair| pair.0 * pair.1)
-        .sum()
+    let is_number = |token: &str| !token.is_empty() && token.bytes().all(|byte| byte.is_ascii_digit());
+    (rest[end..].starts_with(')') && is_number(x) && is_number(y)).then_some(arguments)
 }
 
-pub fn parse_input_2(input: &str) -> Vec<(i64, i64)> {
+pub fn calc_multiplications(pairs: &Vec<(i64, i64)>) -> Result<i64, SolveError> {
 
-    let re = RegexBuilder::new(r"(?:^|do\(\))(.*?)(?:don't\(\)|$)")
-        .multi_line(false)
-        .dot_matches_new_line(true)
-        .build().unwrap();
+    let products = pairs.iter()
+        .map(|pair| pair.0.checked_mul(pair.1).ok_or(SolveError::Overflow))
+        .collect::<Result<Vec<_>, _>>()?;
+    checked_sum(products)
+}
 
-    re.captures_iter(input)
-        .map(|captures| captures.get(1).unwrap().as_str())
-        .map(|str| parse_input(str))
-        .flatten()
-        .collect()
+pub fn parse_input_2(input: &str) -> Result<Vec<(i64, i64)>, ParseError> {
+
+    let mut pairs = Vec::new();
+    let mut rest = input;
+
+    // enabled up to the next `don't()`, then disabled up to the next `do()`
+    loop {
+        let (enabled_section, disabled) = rest.split_once("don't()").unwrap_or((rest, ""));
+        let section_pairs = parse_input(enabled_section).map_err(|err| err.within(input, enabled_section))?;
+        pairs.extend(section_pairs);
+
+        match disabled.split_once("do()") {
+            Some((_, next)) => rest = next,
+            None => break,
+        }
+    }
+
+    Ok(pairs)
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    /// All multiplications, and only the ones enabled by `do()`/`don't()`.
+    type Input = (Vec<(i64, i64)>, Vec<(i64, i64)>);
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok((parse_input(input)?, parse_input_2(input)?))
+    }
+
+    fn part1((pairs, _): &Self::Input) -> Result<i64, SolveError> {
+        calc_multiplications(pairs)
+    }
+
+    fn part2((_, enabled_pairs): &Self::Input) -> Result<i64, SolveError> {
+        calc_multiplications(enabled_pairs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overflow_test() {
+        let pairs = parse_input("mul(3037000499,3037000499)mul(1,1)").unwrap();
+        assert_eq!(calc_multiplications(&pairs), Ok(9_223_372_030_926_249_002));
+
+        let pairs = parse_input("mul(3037000500,3037000500)").unwrap();
+        assert_eq!(calc_multiplications(&pairs), Err(SolveError::Overflow));
+        let pairs = parse_input("mul(3037000499,3037000499)mul(3037000499,2)").unwrap();
+        assert_eq!(calc_multiplications(&pairs), Err(SolveError::Overflow));
+    }
 }
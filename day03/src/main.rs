@@ -1,13 +1,7 @@
-use std::fs;
-use day03::*;
+use std::process::ExitCode;
 
-fn main() {
-    let input = fs::read_to_string("input/input.txt").unwrap();
-    let pairs = parse_input(&input);
-    let result = calc_multiplications(&pairs);
-    println!("Result: {}", result);
+use day03::Day03;
 
-    let pairs_2 = parse_input_2(&input);
-    let result_2 = calc_multiplications(&pairs_2);
-    println!("Result 2: {}", result_2);
+fn main() -> ExitCode {
+    aoc_common::run_main::<Day03>(3)
 }
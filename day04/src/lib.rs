@@ -1,60 +1,25 @@
+use aoc_common::{ParseError, Solution, SolveError};
+use aoc_grid::Grid;
 
-pub fn parse_input(input: &String) -> [Vec<String>; 4] {
-
-    if input.is_empty() {
-        return [Vec::<String>::new(), Vec::<String>::new(), Vec::<String>::new(), Vec::<String>::new()];
-    }
-
-    let lines: Vec<String> = input.trim().split("\n")
-        .map(|line| line.to_string())
-        .collect();
-
-    let chars: Vec<Vec<char>> = lines.iter().map(|str| str.chars().collect::<Vec<char>>()).collect();
-
-    let width = lines[0].len();
-    let height = lines.len();
-
-    let columns: Vec<String> = (0..width).map(|i| chars.iter().map(|row| row[i]).collect::<String>()).collect();
-    
-    let mut diag1: Vec<String> = vec![];
-    for i in 0..width {
-        let mut tmp = Vec::<char>::new();
-
-        for (x,y) in (i..width).zip(0..height) {
-            tmp.push(chars[y][x]);
-        }
+pub fn parse_grid(input: &str) -> Result<Grid<char>, ParseError> {
+    Grid::parse(input, |chr| chr)
+}
 
-        diag1.push(tmp.iter().collect::<String>());
-    }
-    for i in 1..height { 
-        let mut tmp = Vec::<char>::new();
+pub fn parse_input(input: &String) -> Result<[Vec<String>; 4], ParseError> {
+    Ok(grid_lines(&parse_grid(input)?))
+}
 
-        for (x,y) in (0..width).zip(i..height) {
-            tmp.push(chars[y][x]);
-        }
+pub fn grid_lines(grid: &Grid<char>) -> [Vec<String>; 4] {
+    let lines: Vec<String> = grid.rows().map(|row| row.iter().collect()).collect();
+    let columns: Vec<String> = grid.columns().map(|column| column.collect()).collect();
 
-        diag1.push(tmp.iter().collect::<String>());
+    if grid.height() <= 1 {
+        // a single row has no diagonals, only its own characters
+        return [lines, columns, vec![], vec![]];
     }
 
-    let mut diag2: Vec<String> = vec![];
-    for i in 0..width {
-        let mut tmp = Vec::<char>::new();
-
-        for (x,y) in (0..=i).rev().zip(0..height) {
-            tmp.push(chars[y][x]);
-        }
-
-        diag2.push(tmp.iter().collect::<String>());
-    }
-    for i in 1..height {
-        let mut tmp = Vec::<char>::new();
-
-        for (x,y) in (0..width).rev().zip(i..height) {
-            tmp.push(chars[y][x]);
-        }
-
-        diag2.push(tmp.iter().collect::<String>());
-    }
+    let diag1: Vec<String> = grid.diagonals().map(|diag| diag.collect()).collect();
+    let diag2: Vec<String> = grid.anti_diagonals().map(|diag| diag.collect()).collect();
 
     return [lines, columns, diag1, diag2];
 }
@@ -76,23 +41,17 @@ pub fn count_total_xmas(arrays: &[Vec<String>; 4]) -> usize {
     count
 }
 
-pub fn count_x_mas(input: &String) -> usize {
-    let lines: Vec<String> = input.trim().split("\n")
-        .map(|line| line.to_string())
-        .collect();
-
-    let chars: Vec<Vec<char>> = lines.iter().map(|str| str.chars().collect::<Vec<char>>()).collect();
-
-    let width = lines[0].len();
-    let height = lines.len();
+pub fn count_x_mas(grid: &Grid<char>) -> usize {
+    let width = grid.width();
+    let height = grid.height();
 
     let mut count: usize = 0;
 
-    for row in 1..height-1 {
-        for col in 1..width-1 {
-            if chars[row][col] == 'A' {
-                let diag_1 = vec![chars[row-1][col-1], chars[row][col], chars[row+1][col+1]].iter().collect::<String>();
-                let diag_2 = vec![chars[row-1][col+1], chars[row][col], chars[row+1][col-1]].iter().collect::<String>();
+    for row in 1..height.saturating_sub(1) {
+        for col in 1..width.saturating_sub(1) {
+            if grid[(row, col)] == 'A' {
+                let diag_1 = [grid[(row-1, col-1)], grid[(row, col)], grid[(row+1, col+1)]].iter().collect::<String>();
+                let diag_2 = [grid[(row-1, col+1)], grid[(row, col)], grid[(row+1, col-1)]].iter().collect::<String>();
 
                 if (diag_1 == "MAS" || diag_1 == "SAM") && (diag_2 == "MAS" || diag_2 == "SAM")
                 {
@@ -105,6 +64,26 @@ pub fn count_x_mas(input: &String) -> usize {
     return count;
 }
 
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = Grid<char>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_grid(input)
+    }
+
+    fn part1(grid: &Self::Input) -> Result<usize, SolveError> {
+        Ok(count_total_xmas(&grid_lines(grid)))
+    }
+
+    fn part2(grid: &Self::Input) -> Result<usize, SolveError> {
+        Ok(count_x_mas(grid))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -112,21 +91,21 @@ mod tests {
     #[test]
     fn test_parse_input_empty() {
         let input = String::from("");
-        let result = parse_input(&input);
+        let result = parse_input(&input).unwrap();
         assert_eq!(result, [Vec::<String>::new(), Vec::<String>::new(), Vec::<String>::new(), Vec::<String>::new()]);
     }
 
     #[test]
     fn test_parse_input_single_line() {
         let input = String::from("abcd");
-        let result = parse_input(&input);
+        let result = parse_input(&input).unwrap();
         assert_eq!(result, [vec!["abcd"], vec!["a", "b", "c", "d"], vec![], vec![]]);
     }
 
     #[test]
     fn test_parse_input_multiple_lines() {
         let input = String::from("abcd\nefgh\nijkl\nmnop");
-        let result = parse_input(&input);
+        let result = parse_input(&input).unwrap();
         assert_eq!(result, [
             vec!["abcd", "efgh", "ijkl", "mnop"],
             vec!["aeim", "bfjn", "cgko", "dhlp"],
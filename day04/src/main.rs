@@ -1,13 +1,7 @@
-use std::fs;
-use day04::*;
+use std::process::ExitCode;
 
-fn main() {
-    let input = fs::read_to_string("input/input.txt").unwrap();
-    let arrays = parse_input(&input);
+use day04::Day04;
 
-    let total = count_total_xmas(&arrays);
-    println!("Total XMAS: {}", total);
-
-    let total = count_x_mas(&input);
-    println!("Total X-MAS: {}", total);
+fn main() -> ExitCode {
+    aoc_common::run_main::<Day04>(4)
 }
@@ -1,32 +1,41 @@
 use std::collections::HashMap;
 
-pub fn parse_rules(rules_input: &str) -> Vec<(i64, i64)> {
+use aoc_common::{parse_number, ParseError, Solution, SolveError};
+
+pub fn parse_rules(rules_input: &str) -> Result<Vec<(i64, i64)>, ParseError> {
     rules_input
-        .trim()
-        .split("\n")
-        .map(|line| line.split_once("|").unwrap())
-        .map(|pair| {
-            (
-                pair.0.parse::<i64>().unwrap(),
-                pair.1.parse::<i64>().unwrap(),
-            )
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let pair = line
+                .trim()
+                .split_once("|")
+                .ok_or_else(|| ParseError::at(rules_input, line, "a rule like `47|53`"))?;
+            Ok((
+                parse_number(rules_input, pair.0)?,
+                parse_number(rules_input, pair.1)?,
+            ))
         })
         .collect()
 }
 
-pub fn parse_pages(pages_input: &str) -> Vec<Vec<i64>> {
+pub fn parse_pages(pages_input: &str) -> Result<Vec<Vec<i64>>, ParseError> {
     pages_input
-        .trim()
-        .split("\n")
-        .map(|line| line.split(",").map(|i| i.parse::<i64>().unwrap()).collect())
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            line.trim()
+                .split(",")
+                .map(|i| parse_number(pages_input, i))
+                .collect()
+        })
         .collect()
 }
 
 pub fn rules_to_key(rules: &Vec<(i64, i64)>) -> HashMap<i64, i64> {
     let mut all_pages: Vec<i64> = rules
         .iter()
-        .map(|pair| vec![pair.0, pair.1])
-        .flatten()
+        .flat_map(|pair| vec![pair.0, pair.1])
         .collect();
     all_pages.sort();
     all_pages.dedup();
@@ -51,7 +60,7 @@ pub fn rules_to_key(rules: &Vec<(i64, i64)>) -> HashMap<i64, i64> {
         all_pages
             .iter()
             .enumerate()
-            .map(|pair| (*pair.1 as i64, pair.0 as i64)),
+            .map(|pair| (*pair.1, pair.0 as i64)),
     )
 }
 
@@ -94,10 +103,7 @@ pub fn fix_sorting(page: &Vec<i64>, rules: &Vec<(i64, i64)>) -> Vec<i64> {
             let left_idx_opt = new_page.iter().position(|item| item == &rule.0);
             let right_idx_opt = new_page.iter().position(|item| item == &rule.1);
 
-            if left_idx_opt.is_some() && right_idx_opt.is_some() {
-                let left_idx = left_idx_opt.unwrap();
-                let right_idx = right_idx_opt.unwrap();
-
+            if let (Some(left_idx), Some(right_idx)) = (left_idx_opt, right_idx_opt) {
                 if left_idx > right_idx {
                     new_page.swap(left_idx, right_idx);
                     changed = true;
@@ -109,23 +115,63 @@ pub fn fix_sorting(page: &Vec<i64>, rules: &Vec<(i64, i64)>) -> Vec<i64> {
     return new_page;
 }
 
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = (Vec<(i64, i64)>, Vec<Vec<i64>>);
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        // line and column numbers are unaffected by dropping the `\r` of CRLF line ends
+        let input = input.replace("\r\n", "\n");
+        let (rules_input, pages_input) = input
+            .split_once("\n\n")
+            .ok_or_else(|| ParseError::at_end(&input, "a blank line between rules and pages"))?;
+
+        let rules = parse_rules(rules_input).map_err(|err| err.within(&input, rules_input))?;
+        let pages = parse_pages(pages_input).map_err(|err| err.within(&input, pages_input))?;
+        Ok((rules, pages))
+    }
+
+    fn part1((rules, pages): &Self::Input) -> Result<i64, SolveError> {
+        Ok(pages
+            .iter()
+            .filter(|page| is_order_valid(page, rules))
+            .map(get_middle_page)
+            .sum())
+    }
+
+    fn part2((rules, pages): &Self::Input) -> Result<i64, SolveError> {
+        Ok(pages
+            .iter()
+            .filter(|page| !is_order_valid(page, rules))
+            .map(|page| get_middle_page(&fix_sorting(page, rules)))
+            .sum())
+    }
+}
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
     #[test]
     fn test_parse_rules() {
         let input = "1|2\n3|4";
         let expected = vec![(1, 2), (3, 4)];
-        assert_eq!(parse_rules(input), expected);
+        assert_eq!(parse_rules(input).unwrap(), expected);
+
+        let err = parse_rules("1|2\n3-4").unwrap_err();
+        assert_eq!((err.line(), err.column()), (2, 1));
     }
 
     #[test]
     fn test_parse_pages() {
         let input = "1,2,3\n4,5,6";
         let expected = vec![vec![1, 2, 3], vec![4, 5, 6]];
-        assert_eq!(parse_pages(input), expected);
+        assert_eq!(parse_pages(input).unwrap(), expected);
     }
 
     #[test]
@@ -212,4 +258,64 @@ mod tests {
         let expected = HashMap::from_iter(rules.iter().map(|pair| (pair.0, pair.1)));
         assert_eq!(key, expected);
     }
+
+    /// The plain reading of the rules: no page may come after a page that
+    /// a rule wants after it.
+    fn reference_is_order_valid(pages: &[i64], rules: &[(i64, i64)]) -> bool {
+        pages.iter().enumerate().all(|(idx, before)| {
+            pages[idx + 1..]
+                .iter()
+                .all(|after| !rules.contains(&(*after, *before)))
+        })
+    }
+
+    type Rules = Vec<(i64, i64)>;
+
+    /// A hidden order of distinct pages, some of the rules it implies, all of
+    /// them, and an update in random order.
+    fn rules_and_update() -> impl Strategy<Value = (Rules, Rules, Vec<i64>)> {
+        prop::sample::subsequence((10..100).collect::<Vec<i64>>(), 1..12)
+            .prop_shuffle()
+            .prop_flat_map(|order| {
+                let all_rules: Vec<(i64, i64)> = order
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(idx, left)| order[idx + 1..].iter().map(|right| (*left, *right)))
+                    .collect();
+                let some_rules = prop::sample::subsequence(all_rules.clone(), 0..=all_rules.len());
+                let update = prop::sample::subsequence(order.clone(), 1..=order.len());
+                (
+                    some_rules.prop_shuffle(),
+                    Just(all_rules),
+                    update.prop_shuffle(),
+                )
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn is_order_valid_matches_reference((rules, _, update) in rules_and_update()) {
+            let valid = reference_is_order_valid(&update, &rules);
+            prop_assert_eq!(is_order_valid(&update, &rules), valid);
+        }
+
+        // the key only captures the rules when they order every pair of pages
+        #[test]
+        fn is_order_valid_2_matches_reference((_, all_rules, update) in rules_and_update()) {
+            let key = rules_to_key(&all_rules);
+            let valid = reference_is_order_valid(&update, &all_rules);
+            prop_assert_eq!(is_order_valid_2(&update, &key), valid);
+        }
+
+        #[test]
+        fn fix_sorting_is_valid((rules, _, update) in rules_and_update()) {
+            let fixed = fix_sorting(&update, &rules);
+            prop_assert!(reference_is_order_valid(&fixed, &rules));
+
+            let (mut sorted, mut sorted_fixed) = (update.clone(), fixed.clone());
+            sorted.sort();
+            sorted_fixed.sort();
+            prop_assert_eq!(sorted, sorted_fixed);
+        }
+    }
 }
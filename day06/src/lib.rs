@@ -1,187 +1,317 @@
+use aoc_anim::{Animate, Cell, Color, Frame, Inspect, Picture, Render, StepThrough};
+use aoc_common::{par_map, ParseError, Simulation, Solution, SolveError};
+use aoc_grid::{Direction, Grid, Pos};
+use tracing::{debug, trace};
+
 #[derive(Copy, Clone)]
 #[derive(PartialEq)]
-pub enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-impl Direction {
-    fn vector(&self) -> (i64, i64) {
-        match *self {
-            Direction::Up => (-1, 0),
-            Direction::Down => (1, 0),
-            Direction::Left => (0, -1),
-            Direction::Right => (0, 1),
-        }
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MapObject {
+    Nothing,
+    Obstacle,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LabMap {
+    lab_map: Grid<MapObject>,
+}
+
+impl LabMap {
+    /// Object at `pos`, or `None` outside of the map.
+    fn object_at(&self, pos: &Pos) -> Option<MapObject> {
+        self.lab_map.get_pos(*pos).copied()
+    }
+
+    pub fn clone_with_additional_obstacle(&self, pos: &Pos) -> LabMap {
+        let mut new_map = self.clone();
+        new_map.lab_map[*pos] = MapObject::Obstacle;
+        return new_map;
     }
+}
+
+pub fn parse_input(str: &str) -> Result<(LabMap, Pos), ParseError> {
+    let chars = Grid::try_parse(str, "one of `.`, `#`, `^`", |chr| {
+        matches!(chr, '.' | '#' | '^').then_some(chr)
+    })?;
+
+    let guard_pos = chars
+        .find(&'^')
+        .map(Pos::from)
+        .ok_or_else(|| ParseError::at_end(str, "a guard `^` somewhere on the map"))?;
+
+    let lab_map = chars.map(|item| match item {
+        '#' => MapObject::Obstacle,
+        _ => MapObject::Nothing,
+    });
+
+    Ok((LabMap { lab_map }, guard_pos))
+}
 
-    fn turn_right(&self) -> Direction {
-        match *self {
-            Direction::Up => Direction::Right,
-            Direction::Down => Direction::Left,
-            Direction::Left => Direction::Up,
-            Direction::Right => Direction::Down,
+pub enum GuardStepResult {
+    PatrolEnd,
+    NextStep(Pos, Direction),
+}
+
+pub fn simulate_one_guard_step(
+    map: &LabMap,
+    init_pos: &Pos,
+    init_dir: &Direction,
+) -> GuardStepResult {
+
+    let mut next_dir = init_dir.to_owned();
+    let mut next_pos = *init_pos + next_dir.vec();
+
+    loop{
+        let Some(next_object) = map.object_at(&next_pos) else {
+            return GuardStepResult::PatrolEnd;
+        };
+
+        let is_facing_obstacle = next_object == MapObject::Obstacle;
+        if !is_facing_obstacle {
+            break;
         }
+
+        next_dir = next_dir.turn_right();
+        next_pos = *init_pos + next_dir.vec();
     }
+
+    GuardStepResult::NextStep(next_pos, next_dir)
 }
 
-#[derive(Clone, Copy)]
-pub struct Position {
-    y: i64,
-    x: i64,
+
+/// The guard alone, with what is needed to move it.
+#[derive(Clone)]
+pub struct Guard<'a> {
+    map: &'a LabMap,
+    /// Where the guard is and where it faces, `None` once it left the map.
+    pub position: Option<(Pos, Direction)>,
 }
 
-impl Position {
-    fn make_step(&self, dir: &Direction) -> Position {
-        let vector = dir.vector();
-        Position {
-            y: self.y + vector.0,
-            x: self.x + vector.1,
+impl<'a> Guard<'a> {
+    pub fn new(map: &'a LabMap, pos: Pos, dir: Direction) -> Guard<'a> {
+        Guard {
+            map,
+            position: Some((pos, dir)),
         }
     }
+}
+
+impl Simulation for Guard<'_> {
+    type State = Option<(Pos, Direction)>;
 
-    pub fn x(&self) -> i64 {
-        self.x
+    fn state(&self) -> Self::State {
+        self.position
     }
 
-    pub fn y(&self) -> i64 {
-        self.y
+    fn step(&mut self) -> bool {
+        let Some((pos, dir)) = self.position else {
+            return false;
+        };
+
+        self.position = match simulate_one_guard_step(self.map, &pos, &dir) {
+            GuardStepResult::PatrolEnd => None,
+            GuardStepResult::NextStep(new_pos, new_dir) => Some((new_pos, new_dir)),
+        };
+        true
     }
 }
 
-impl PartialEq for Position{
-    fn eq(&self, other: &Self) -> bool {
-        return self.x == other.x && self.y == other.y;
-    }
+pub fn is_path_looping(map: &LabMap, guard_pos: &Pos, guard_dir: &Direction) -> bool {
+    Guard::new(map, *guard_pos, *guard_dir).find_cycle().is_some()
 }
 
-#[derive(Copy, Clone)]
-#[derive(PartialEq)]
-pub enum MapObject {
-    Nothing,
-    Obstacle,
+pub fn find_patrol_positions(
+    map: &LabMap,
+    guard_pos: &Pos,
+    guard_dir: &Direction,
+) -> Vec<Pos> {
+    let mut guard = Guard::new(map, *guard_pos, *guard_dir);
+    let mut positions = vec![*guard_pos];
+
+    while guard.step() {
+        positions.extend(guard.position.map(|(pos, _)| pos));
+    }
+
+    positions.sort();
+    positions.dedup();
+    positions
 }
 
+/// The guard's patrol so far, one step at a time.
 #[derive(Clone)]
-pub struct LabMap {
-    lab_map: Vec<Vec<MapObject>>,
+pub struct Patrol<'a> {
+    guard: Guard<'a>,
+    visited: Grid<bool>,
+    steps: usize,
 }
 
-impl LabMap {
-    fn object_at(&self, pos: &Position) -> MapObject {
-        self.lab_map[pos.y as usize][pos.x as usize]
-    }
+impl<'a> Patrol<'a> {
+    pub fn new(map: &'a LabMap, guard_pos: Pos, guard_dir: Direction) -> Patrol<'a> {
+        let mut visited = map.lab_map.map(|_| false);
+        visited[guard_pos] = true;
 
-    fn width(&self) -> usize {
-        self.lab_map[0].len()
+        Patrol {
+            guard: Guard::new(map, guard_pos, guard_dir),
+            visited,
+            steps: 0,
+        }
     }
+}
 
-    fn height(&self) -> usize {
-        self.lab_map.len()
-    }
+impl Simulation for Patrol<'_> {
+    type State = Option<(Pos, Direction)>;
 
-    fn is_invalid_pos(&self, pos: &Position) -> bool{
-        pos.y < 0
-        || pos.y >= self.height() as i64
-        || pos.x < 0
-        || pos.x >= self.width() as i64
+    fn state(&self) -> Self::State {
+        self.guard.state()
     }
 
-    pub fn clone_with_additional_obstacle(&self, pos: &Position) -> LabMap {
-        let mut new_map = self.clone();
-        new_map.lab_map[pos.y as usize][pos.x as usize] = MapObject::Obstacle;
-        return new_map;
+    fn step(&mut self) -> bool {
+        if !self.guard.step() {
+            return false;
+        }
+
+        self.steps += 1;
+        if let Some((pos, _)) = self.guard.position {
+            self.visited[pos] = true;
+        }
+        true
     }
 }
 
-pub fn parse_input(str: &str) -> (LabMap, Position) {
-    let mut guard_pos = Position { y: 0, x: 0 };
+impl Render for Patrol<'_> {
+    fn render(&self) -> Frame {
+        let mut frame = Frame::from_grid(&self.guard.map.lab_map, |object| match object {
+            MapObject::Obstacle => Cell::new('#').fg(Color::Gray),
+            MapObject::Nothing => Cell::new('.').fg(Color::Gray),
+        });
 
-    let lab_map: Vec<Vec<MapObject>> = str
-        .trim()
-        .split("\n")
-        .map(|line| {
-            line.chars()
-                .map(|item| match item {
-                    '#' => MapObject::Obstacle,
-                    _ => MapObject::Nothing,
-                })
-                .collect()
-        })
-        .collect();
-
-    for line in str.split("\n").enumerate() {
-        let guard_pos_found = line.1.find("^");
-        match guard_pos_found {
-            Some(pos) => {
-                guard_pos = Position {
-                    y: line.0 as i64,
-                    x: pos as i64,
-                };
-                break;
+        for (idx, _) in self.visited.iter().filter(|(_, visited)| **visited) {
+            frame.set(Pos::from(idx), Cell::new('X').fg(Color::Yellow));
+        }
+
+        let visited = self.visited.iter().filter(|(_, visited)| **visited).count();
+        match self.guard.position {
+            Some((pos, dir)) => {
+                frame.set(pos, Cell::new(dir.to_arrow()).fg(Color::Red));
+                frame.with_caption(format!("step {}, {visited} positions visited", self.steps))
             }
-            None => continue,
+            None => frame.with_caption(format!(
+                "the guard left after {} steps, {visited} positions visited",
+                self.steps
+            )),
         }
     }
-
-    (LabMap { lab_map }, guard_pos)
 }
 
-pub enum GuardStepResult {
-    PatrolEnd,
-    NextStep(Position, Direction),
+impl Inspect for Patrol<'_> {
+    fn details(&self) -> Vec<(&'static str, String)> {
+        let guard = match self.guard.position {
+            Some((pos, dir)) => format!("{pos} facing {}", dir.to_arrow()),
+            None => "gone".to_string(),
+        };
+        let visited = self.visited.iter().filter(|(_, visited)| **visited).count();
+        vec![("guard", guard), ("visited", visited.to_string())]
+    }
+
+    fn focus(&self) -> Option<Pos> {
+        self.guard.position.map(|(pos, _)| pos)
+    }
 }
 
-pub fn simulate_one_guard_step(
-    map: &LabMap,
-    init_pos: &Position,
-    init_dir: &Direction,
-) -> GuardStepResult {
+pub struct Day06;
 
-    let mut next_dir = init_dir.to_owned();
-    let mut next_pos = init_pos.make_step(&next_dir);
+impl Solution for Day06 {
+    type Input = (LabMap, Pos);
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-    loop{
-        let is_next_pos_out_of_bounds = map.is_invalid_pos(&next_pos);
-        if is_next_pos_out_of_bounds {
-            return GuardStepResult::PatrolEnd;
-        }
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
 
-        let is_facing_obstacle = map.object_at(&next_pos) == MapObject::Obstacle;
-        if !is_facing_obstacle {
-            break;
-        }
+    fn part1((map, guard_pos): &Self::Input) -> Result<usize, SolveError> {
+        Ok(find_patrol_positions(map, guard_pos, &Direction::Up).len())
+    }
 
-        next_dir = next_dir.turn_right();
-        next_pos = init_pos.make_step(&next_dir);
+    fn part2((map, guard_pos): &Self::Input) -> Result<usize, SolveError> {
+        let patrol_positions = find_patrol_positions(map, guard_pos, &Direction::Up);
+        debug!(
+            candidates = patrol_positions.len(),
+            "trying an obstacle on every patrolled position"
+        );
+
+        let possible_obstacle_placements = par_map(&patrol_positions, |pos| {
+            let altered_map = map.clone_with_additional_obstacle(pos);
+            let is_looping = is_path_looping(&altered_map, guard_pos, &Direction::Up);
+            if is_looping {
+                trace!(%pos, "an obstacle here makes the guard walk in circles");
+            }
+            is_looping
+        })
+        .into_iter()
+        .filter(|is_looping| *is_looping)
+        .count();
+
+        Ok(possible_obstacle_placements)
     }
+}
 
-    GuardStepResult::NextStep(next_pos, next_dir)
+impl Animate for Day06 {
+    fn frames((map, guard_pos): &Self::Input) -> impl Iterator<Item = Frame> + '_ {
+        let mut patrol = Patrol::new(map, *guard_pos, Direction::Up);
+
+        std::iter::once(patrol.render())
+            .chain(std::iter::from_fn(move || patrol.step().then(|| patrol.render())))
+    }
 }
 
+impl StepThrough for Day06 {
+    fn simulation((map, guard_pos): &Self::Input) -> impl Inspect + '_ {
+        Patrol::new(map, *guard_pos, Direction::Up)
+    }
+}
 
-pub fn is_path_looping(map: &LabMap, guard_pos: &Position, guard_dir: &Direction) -> bool {
+impl Picture for Day06 {
+    /// The lab once the guard has left it.
+    fn picture((map, guard_pos): &Self::Input) -> Frame {
+        let mut patrol = Patrol::new(map, *guard_pos, Direction::Up);
+        while patrol.step() {}
+        patrol.render()
+    }
+}
 
-    let mut pos = guard_pos.to_owned();
-    let mut dir = guard_dir.to_owned();
+#[cfg(test)]
+mod tests {
+    use aoc_common::Cycle;
 
-    let mut history: Vec<(Position, Direction)> = vec![(pos, dir)];
+    use super::*;
 
-    loop {
-        let result = simulate_one_guard_step(&map, &pos, &dir);
-        match result {
-            GuardStepResult::PatrolEnd => return false,
-            GuardStepResult::NextStep(new_pos, new_dir) => {
-                let already_visited = history.contains(&(new_pos, new_dir));
-                if already_visited {
-                    return true;
-                }
-                pos = new_pos;
-                dir = new_dir;
-                history.push((pos, dir));
-            },
-        }
+    #[test]
+    fn frames_test() {
+        let input = parse_input(include_str!("../input/test_input.txt")).unwrap();
+        let frames: Vec<Frame> = Day06::frames(&input).collect();
+
+        let first = frames.first().unwrap().to_string();
+        assert!(first.starts_with("....#.....\n.........#"));
+        assert_eq!(first.matches('^').count(), 1);
+        assert_eq!(first.matches('X').count(), 0);
+        let last = frames.last().unwrap().to_string();
+        assert_eq!(last.matches('X').count(), 41);
+        assert!(last.ends_with("41 positions visited"));
+    }
+
+    #[test]
+    fn looping_test() {
+        let (map, guard_pos) = parse_input(include_str!("../input/test_input.txt")).unwrap();
+        assert!(!is_path_looping(&map, &guard_pos, &Direction::Up));
+
+        // right next to the guard's start, sending it round the rectangle
+        // between rows 1 and 6 and columns 4 and 8
+        let map = map.clone_with_additional_obstacle(&Pos::new(6, 3));
+        assert!(is_path_looping(&map, &guard_pos, &Direction::Up));
+        let cycle = Guard::new(&map, guard_pos, Direction::Up).find_cycle().unwrap();
+        // it comes back to the start facing left, not up
+        assert_eq!(cycle, Cycle { start: 1, period: 18 });
     }
-}
\ No newline at end of file
+}
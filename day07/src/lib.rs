@@ -1,22 +1,35 @@
 use std::fmt::{self};
 
-pub fn parse_input(input: &str) -> Vec<(i64, Vec<i64>)> {
-    let parse_args = |str: &str| -> Vec<i64> {
-        str.split(" ")
-            .map(|item| item.parse::<i64>().unwrap())
-            .collect()
+use aoc_common::{checked_sum, par_map, parse_number, ParseError, Solution, SolveError};
+
+pub fn parse_input(input: &str) -> Result<Vec<(i64, Vec<i64>)>, ParseError> {
+    // a negative number could bring an overflowed result back, see `calculate_ops`
+    let parse_value = |item: &str| -> Result<i64, ParseError> {
+        match parse_number(input, item)? {
+            value if value < 0 => Err(ParseError::at(input, item, "a non-negative number")),
+            value => Ok(value),
+        }
+    };
+    let parse_args = |str: &str| -> Result<Vec<i64>, ParseError> {
+        str.split_whitespace().map(parse_value).collect()
     };
 
-    let parse_line = |line: &str| -> (i64, Vec<i64>) {
-        let pair = line.split_once(": ").unwrap();
-        (pair.0.parse::<i64>().unwrap(), parse_args(pair.1))
+    let parse_line = |line: &str| -> Result<(i64, Vec<i64>), ParseError> {
+        let pair = line
+            .split_once(": ")
+            .ok_or_else(|| ParseError::at(input, line, "`<test value>: <numbers>`"))?;
+        let args = parse_args(pair.1)?;
+        if args.is_empty() {
+            return Err(ParseError::at(input, pair.1, "at least one number"));
+        }
+        Ok((parse_value(pair.0)?, args))
     };
 
     input
-        .trim()
-        .split("\n")
+        .lines()
+        .filter(|line| !line.trim().is_empty())
         .map(parse_line)
-        .collect::<Vec<(i64, Vec<i64>)>>()
+        .collect()
 }
 
 #[derive(Clone, Copy)]
@@ -27,14 +40,15 @@ enum Ops {
 }
 
 impl Ops {
-    pub fn calc(&self, a: i64, b: i64) -> i64 {
+    /// `None` if the result does not fit in an `i64`.
+    pub fn calc(&self, a: i64, b: i64) -> Option<i64> {
         match *self {
-            Ops::Mul => a * b,
-            Ops::Add => a + b,
+            Ops::Mul => a.checked_mul(b),
+            Ops::Add => a.checked_add(b),
             Ops::Concat => {
-                let exp = (b as f32).log10().floor() as u32;
-                let kek = a * 10_i64.pow(exp + 1);
-                kek + b
+                let digits = b.checked_ilog10().unwrap_or(0) + 1;
+                let kek = a.checked_mul(10_i64.checked_pow(digits)?)?;
+                kek.checked_add(b)
             }
         }
     }
@@ -55,8 +69,7 @@ impl fmt::Display for Ops {
 }
 
 fn generate_ops_combinations(count: usize, ops: &Vec<Ops>) -> Vec<Vec<Ops>> {
-    let mut out = Vec::<Vec<Ops>>::new();
-    out.push(vec![]);
+    let mut out: Vec<Vec<Ops>> = vec![vec![]];
 
     for _ in 0..count {
         let mut new_out = Vec::<Vec<Ops>>::new();
@@ -75,11 +88,18 @@ fn generate_ops_combinations(count: usize, ops: &Vec<Ops>) -> Vec<Vec<Ops>> {
     out
 }
 
-fn calculate_ops(ops: &Vec<Ops>, args: &Vec<i64>) -> i64 {
-    let mut result = args[0];
+/// `None` if the result does not fit in an `i64`. With non-negative
+/// operands, an intermediate result that overflows is past any test value,
+/// unless a later `Mul` by zero brings it back down.
+fn calculate_ops(ops: &Vec<Ops>, args: &Vec<i64>) -> Option<i64> {
+    let mut result = Some(args[0]);
 
     for i in 0..ops.len() {
-        result = ops[i].calc(result, args[i + 1]);
+        result = match (ops[i], result) {
+            (Ops::Mul, _) if args[i + 1] == 0 => Some(0),
+            (op, Some(value)) => op.calc(value, args[i + 1]),
+            (_, None) => None,
+        };
     }
 
     return result;
@@ -89,7 +109,7 @@ fn validate_entry(test_value: &i64, args: &Vec<i64>, possible_ops: &Vec<Ops>) ->
     let possible_ops = generate_ops_combinations(args.len() - 1, possible_ops);
 
     for ops in possible_ops {
-        if calculate_ops(&ops, &args) == *test_value {
+        if calculate_ops(&ops, args) == Some(*test_value) {
             return true;
         }
     }
@@ -104,17 +124,59 @@ pub fn validate_entry_add_mul_concat(test_value: &i64, args: &Vec<i64>) -> bool
     return validate_entry(test_value, args, &vec![Ops::Add, Ops::Mul, Ops::Concat]);
 }
 
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input = Vec<(i64, Vec<i64>)>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(entries: &Self::Input) -> Result<i64, SolveError> {
+        let values = par_map(entries, |(test_value, args)| {
+            validate_entry_add_mul(test_value, args).then_some(*test_value)
+        });
+        checked_sum(values.into_iter().flatten())
+    }
+
+    fn part2(entries: &Self::Input) -> Result<i64, SolveError> {
+        let values = par_map(entries, |(test_value, args)| {
+            validate_entry_add_mul_concat(test_value, args).then_some(*test_value)
+        });
+        checked_sum(values.into_iter().flatten())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn parse_test() {
+        let entries = parse_input("190: 10 19\n3267: 81 40 27\n").unwrap();
+        assert_eq!(entries, vec![(190, vec![10, 19]), (3267, vec![81, 40, 27])]);
+
+        let err = parse_input("190: 10 19\n5: \n").unwrap_err();
+        assert_eq!((err.line(), err.column()), (2, 4));
+        assert_eq!(err.expected(), "at least one number");
+
+        // negative numbers could bring an overflowed result back in range
+        let err = parse_input("9223372036854775803: 9223372036854775807 1 -5").unwrap_err();
+        assert_eq!((err.line(), err.column()), (1, 44));
+        assert_eq!(err.expected(), "a non-negative number");
+        assert!(parse_input("-5: 1").is_err());
+    }
+
     #[test]
     fn calculate_ops_test() {
         let res = calculate_ops(&vec![Ops::Add], &vec![1, 2]);
-        assert_eq!(res, 3);
+        assert_eq!(res, Some(3));
 
         let res = calculate_ops(&vec![Ops::Mul], &vec![3, 2]);
-        assert_eq!(res, 6);
+        assert_eq!(res, Some(6));
     }
 
     #[test]
@@ -156,6 +218,36 @@ mod tests {
     #[test]
     fn ops_calc_test() {
         let x = Ops::Concat.calc(100, 100);
-        assert_eq!(x, 100100);
+        assert_eq!(x, Some(100100));
+        assert_eq!(Ops::Concat.calc(12, 0), Some(120));
+        // too many digits for an `f32` to count them right
+        assert_eq!(Ops::Concat.calc(1, 999_999_999), Some(1_999_999_999));
+    }
+
+    #[test]
+    fn overflow_test() {
+        assert_eq!(Ops::Add.calc(i64::MAX - 1, 1), Some(i64::MAX));
+        assert_eq!(Ops::Add.calc(i64::MAX, 1), None);
+        assert_eq!(Ops::Mul.calc(i64::MAX / 2, 3), None);
+        assert_eq!(Ops::Concat.calc(922_337_203_685_477_580, 7), Some(i64::MAX));
+        assert_eq!(Ops::Concat.calc(922_337_203_685_477_580, 8), None);
+        assert_eq!(Ops::Concat.calc(1, i64::MAX), None);
+
+        // `Mul` overflows on the way, `Add` reaches the test value
+        let args = vec![i64::MAX - 2, 2];
+        assert!(validate_entry_add_mul(&i64::MAX, &args));
+        assert!(!validate_entry_add_mul(&i64::MIN, &args));
+
+        // the product overflows, but multiplying by zero brings it back
+        let args = vec![i64::MAX, 2, 0];
+        assert!(validate_entry_add_mul(&0, &args));
+        assert!(validate_entry_add_mul_concat(&0, &args));
+
+        let entries = vec![(i64::MAX, vec![i64::MAX]), (1, vec![1])];
+        assert_eq!(Day07::part1(&entries), Err(SolveError::Overflow));
+        assert_eq!(
+            Day07::part2(&vec![(i64::MAX, vec![i64::MAX])]),
+            Ok(i64::MAX)
+        );
     }
 }
@@ -1,31 +1,25 @@
-use std::{cmp::min, collections::HashMap, usize};
+use std::{cmp::min, collections::HashMap};
 
-#[derive(Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Debug)]
-pub struct Pos {
-    pub y: i64,
-    pub x: i64,
-}
+use aoc_common::{ParseError, Solution, SolveError};
+use aoc_grid::Pos;
 
 pub fn get_map_size(input: &str) -> (usize, usize) {
-    let height = input.chars().filter(|c| *c == '\n').count() + 1;
-    let width = input.split_once('\n').unwrap().0.trim().len();
+    let height = input.trim().lines().count();
+    let width = input
+        .trim()
+        .lines()
+        .next()
+        .map_or(0, |line| line.trim().len());
     (height, width)
 }
 
 fn calc_nodes_for_pair(pos1: &Pos, pos2: &Pos) -> (Pos, Pos) {
-    let dy = pos2.y - pos1.y;
-    let dx = pos2.x - pos1.x;
-
-    (
-        Pos {
-            y: pos1.y - dy,
-            x: pos1.x - dx,
-        },
-        Pos {
-            y: pos2.y + dy,
-            x: pos2.x + dx,
-        },
-    )
+    let delta = *pos2 - *pos1;
+    (*pos1 - delta, *pos2 + delta)
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 { a } else { gcd(b, a % b) }
 }
 
 fn calc_nodes_for_pair_in_bounds(
@@ -34,26 +28,21 @@ fn calc_nodes_for_pair_in_bounds(
     map_height: usize,
     map_width: usize,
 ) -> Vec<Pos> {
-    let mut dy = pos2.y - pos1.y;
-    let mut dx = pos2.x - pos1.x;
-
-    for i in 2..=min(dx.abs(), dy.abs()) {
-        if dx % i == 0 && dy % i == 0 {
-            dx /= i;
-            dy /= i;
-        }
+    // the smallest step along the line, so that no antinode in between is missed
+    let mut delta = *pos2 - *pos1;
+    let divisor = gcd(delta.x.unsigned_abs(), delta.y.unsigned_abs()) as i64;
+    if divisor > 1 {
+        delta.x /= divisor;
+        delta.y /= divisor;
     }
 
     let mut nodes = Vec::<Pos>::new();
 
     let mut i = 0;
     loop {
-        let new_pos = Pos {
-            y: pos1.y + dy * i,
-            x: pos1.x + dx * i,
-        };
+        let new_pos = *pos1 + delta * i;
 
-        if is_valid_pos(&new_pos, map_height, map_width) {
+        if new_pos.in_bounds(map_width, map_height) {
             nodes.push(new_pos);
             i += 1;
             continue;
@@ -64,12 +53,9 @@ fn calc_nodes_for_pair_in_bounds(
 
     let mut i = -1;
     loop {
-        let new_pos = Pos {
-            y: pos1.y + dy * i,
-            x: pos1.x + dx * i,
-        };
+        let new_pos = *pos1 + delta * i;
 
-        if is_valid_pos(&new_pos, map_height, map_width) {
+        if new_pos.in_bounds(map_width, map_height) {
             nodes.push(new_pos);
             i -= 1;
             continue;
@@ -121,30 +107,101 @@ pub fn calc_node_positions_for_antenna_type_2(
     return nodes;
 }
 
-pub fn parse_input(input: &str) -> HashMap<char, Vec<Pos>> {
+pub fn parse_input(input: &str) -> Result<HashMap<char, Vec<Pos>>, ParseError> {
     let mut map = HashMap::<char, Vec<Pos>>::new();
+    let (_, width) = get_map_size(input);
 
-    let lines = input.trim().split("\n").enumerate();
+    let lines = input.trim().lines().map(str::trim).enumerate();
 
     for (y, line) in lines {
         for (x, item) in line.char_indices() {
             if item == '.' {
                 continue;
             }
+            if !item.is_ascii_alphanumeric() {
+                return Err(ParseError::at(
+                    input,
+                    &line[x..],
+                    "`.` or an antenna frequency",
+                ));
+            }
 
             let entry = map.entry(item).or_insert(vec![]);
-            entry.push(Pos {
-                y: y as i64,
-                x: x as i64,
-            });
+            entry.push(Pos::from((y, x)));
+        }
+
+        // every character is ASCII at this point, so byte offsets are columns
+        if line.len() != width {
+            let end = min(line.len(), width);
+            return Err(ParseError::at(
+                input,
+                &line[end..],
+                format!("a row of {width} cells"),
+            ));
         }
     }
 
-    return map;
+    return Ok(map);
+}
+
+fn count_unique_valid_nodes(
+    nodes: impl Iterator<Item = Pos>,
+    map_height: usize,
+    map_width: usize,
+) -> usize {
+    let mut valid_nodes: Vec<Pos> = nodes
+        .filter(|node_pos| node_pos.in_bounds(map_width, map_height))
+        .collect();
+
+    valid_nodes.sort();
+    valid_nodes.dedup();
+    valid_nodes.len()
 }
 
-pub fn is_valid_pos(pos: &Pos, map_height: usize, map_width: usize) -> bool {
-    pos.x >= 0 && pos.y >= 0 && (pos.x as usize) < map_width && (pos.y as usize) < map_height
+pub struct Day08;
+
+impl Solution for Day08 {
+    /// Antenna positions by frequency, and the map size as `(height, width)`.
+    type Input = (HashMap<char, Vec<Pos>>, (usize, usize));
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok((parse_input(input)?, get_map_size(input)))
+    }
+
+    fn part1((antennas_map, (map_height, map_width)): &Self::Input) -> Result<usize, SolveError> {
+        let nodes = antennas_map
+            .values()
+            .flat_map(|antenna_positions| calc_node_positions_for_antenna_type(antenna_positions));
+
+        Ok(count_unique_valid_nodes(nodes, *map_height, *map_width))
+    }
+
+    fn part2((antennas_map, (map_height, map_width)): &Self::Input) -> Result<usize, SolveError> {
+        let nodes = antennas_map.values().flat_map(|antenna_positions| {
+            calc_node_positions_for_antenna_type_2(antenna_positions, *map_height, *map_width)
+        });
+
+        Ok(count_unique_valid_nodes(nodes, *map_height, *map_width))
+    }
+}
+
+#[test]
+fn test_calc_nodes_for_pair_in_bounds() {
+    // (4, 8) apart, so every fourth tile on the line is an antinode
+    let pos1 = Pos { y: 0, x: 0 };
+    let pos2 = Pos { y: 4, x: 8 };
+    let mut nodes = calc_nodes_for_pair_in_bounds(&pos1, &pos2, 5, 9);
+    nodes.sort();
+    let exp_nodes = [(0, 0), (1, 2), (2, 4), (3, 6), (4, 8)].map(|(y, x)| Pos { y, x });
+    assert_eq!(nodes, exp_nodes);
+
+    // in a straight line, one tile apart
+    let pos1 = Pos { y: 2, x: 0 };
+    let pos2 = Pos { y: 2, x: 3 };
+    let nodes = calc_nodes_for_pair_in_bounds(&pos1, &pos2, 5, 5);
+    assert_eq!(nodes.len(), 5);
 }
 
 #[test]
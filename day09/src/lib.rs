@@ -1,13 +1,17 @@
-use std::{iter::zip, usize};
+use std::iter::zip;
 
-pub fn unroll_input(input: &str) -> Vec<Option<u32>> {
+use aoc_common::{ParseError, Solution, SolveError};
+
+pub fn unroll_input(input: &str) -> Result<Vec<Option<u32>>, ParseError> {
     let mut out = Vec::<Option<u32>>::with_capacity(input.len());
 
     let mut is_file = true;
     let mut id_counter: u32 = 0;
 
-    for chr in input.chars() {
-        let size: u32 = chr.to_string().parse().unwrap();
+    for (idx, chr) in input.char_indices() {
+        let size = chr
+            .to_digit(10)
+            .ok_or_else(|| ParseError::at(input, &input[idx..], "a digit"))?;
 
         if is_file {
             for _ in 0..size {
@@ -23,7 +27,7 @@ pub fn unroll_input(input: &str) -> Vec<Option<u32>> {
         is_file ^= true;
     }
 
-    return out;
+    return Ok(out);
 }
 
 pub fn mem_to_str(vec: &Vec<Option<u32>>) -> String {
@@ -106,15 +110,9 @@ fn find_free_span(mem: &[Option<u32>], size: usize) -> Option<(usize, usize)> {
 }
 
 fn find_file_span(mem: &[Option<u32>], file_id: u32) -> Option<(usize, usize)> {
-    let start_idx_opt = mem
+    let start_idx = mem
         .iter()
-        .position(|item| item.is_some() && item.unwrap() == file_id);
-
-    if start_idx_opt.is_none() {
-        return None;
-    }
-
-    let start_idx = start_idx_opt.unwrap();
+        .position(|item| item.is_some() && item.unwrap() == file_id)?;
 
     let mut span_len = 0;
     while start_idx + span_len < mem.len() && mem[start_idx + span_len] == Some(file_id) {
@@ -160,6 +158,27 @@ pub fn rearrange_smart(mem: &[Option<u32>]) -> Vec<Option<u32>> {
     return out;
 }
 
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input = Vec<Option<u32>>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let disk_map = input.trim();
+        unroll_input(disk_map).map_err(|err| err.within(input, disk_map))
+    }
+
+    fn part1(unrolled: &Self::Input) -> Result<u64, SolveError> {
+        Ok(calc_checksum(&rearrange_simple(unrolled)))
+    }
+
+    fn part2(unrolled: &Self::Input) -> Result<u64, SolveError> {
+        Ok(calc_checksum(&rearrange_smart(unrolled)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -168,7 +187,7 @@ mod tests {
     fn it_works_1() {
         let input = "2333133121414131402";
 
-        let unrolled = unroll_input(input);
+        let unrolled = unroll_input(input).unwrap();
         let unrolled_str = mem_to_str(&unrolled);
         let unrolled_str_exp = "00...111...2...333.44.5555.6666.777.888899";
         assert_eq!(unrolled_str_exp, unrolled_str);
@@ -188,10 +207,16 @@ mod tests {
         assert_eq!(rearranged_smart_str_exp, rearranged_smart_str);
     }
 
+    #[test]
+    fn parse_error_test() {
+        let err = Day09::parse("\n  23x1\n").unwrap_err();
+        assert_eq!((err.line(), err.column()), (2, 5));
+    }
+
     #[test]
     fn find_span_test() {
         let input = "214";
-        let unrolled = unroll_input(input);
+        let unrolled = unroll_input(input).unwrap();
         let span = find_file_span(&unrolled, 1);
         assert_eq!((3, 4), span.unwrap());
     }
@@ -1,22 +1,7 @@
-use std::fs;
+use std::process::ExitCode;
 
-use day09::*;
+use day09::Day09;
 
-fn main() {
-    let input_path = "input/input.txt";
-   // let input_path = "input/test_input.txt";
-
-    let input = fs::read_to_string(input_path).unwrap();
-
-    let unrolled = unroll_input(&input);
-    let rearranged = rearrange_simple(&unrolled);
-
-    let result = calc_checksum(&rearranged);
-    println!("Result: {result}");
-
-    let rearranged_2 = rearrange_smart(&unrolled);
-    let result_2 = calc_checksum(&rearranged_2);
-    println!("Result 2: {result_2}");
+fn main() -> ExitCode {
+    aoc_common::run_main::<Day09>(9)
 }
-
-
@@ -1,71 +1,32 @@
-use std::char;
+use aoc_anim::{Cell, Color, Frame, Picture};
+use aoc_common::{par_map, ParseError, Solution, SolveError};
+use aoc_grid::{Grid, Idx};
+use tracing::debug;
 
-pub type Pos = (usize, usize);
+pub type Pos = Idx;
 pub type Trail = Vec<Pos>;
-pub type TopoMap = Vec<Vec<u8>>;
+pub type TopoMap = Grid<u8>;
 
-fn map_width(topo_map: &TopoMap) -> usize {
-    return topo_map[0].len();
-}
-
-fn map_height(topo_map: &TopoMap) -> usize {
-    return topo_map.len();
-}
-
-pub fn parse_input(input: &str) -> TopoMap {
-    let parse_line = |line: &str| {
-        line.chars()
-            .map(|c| c.to_string().parse().unwrap())
-            .collect()
-    };
-
-    input.trim().split("\n").map(parse_line).collect()
+pub fn parse_input(input: &str) -> Result<TopoMap, ParseError> {
+    Grid::try_parse(input, "a height digit", |c| c.to_digit(10).map(|d| d as u8))
 }
 
 pub fn find_start_points(topo_map: &TopoMap) -> Vec<Pos> {
-    let mut start_points = Vec::<Pos>::new();
-
-    for (line_idx, line) in topo_map.iter().enumerate() {
-        for (column_idx, val) in line.iter().enumerate() {
-            if *val == 0 {
-                start_points.push((line_idx, column_idx));
-            }
-        }
-    }
-
-    return start_points;
+    return topo_map.positions_of(&0).collect();
 }
 
 pub fn find_trails_from(topo_map: &TopoMap, start_position: &Pos) -> Vec<Trail> {
-    let current_elevation = topo_map[start_position.0][start_position.1];
+    let current_elevation = topo_map[*start_position];
 
     if current_elevation == 9 {
         let trail = vec![start_position.to_owned()];
         return vec![trail];
     }
 
-    let mut possible_dirs = Vec::<(i8, i8)>::new();
-    if start_position.0 > 0 {
-        possible_dirs.push((-1, 0));
-    }
-    if start_position.1 > 0 {
-        possible_dirs.push((0, -1));
-    }
-    if start_position.0 < map_height(topo_map) - 1 {
-        possible_dirs.push((1, 0));
-    }
-    if start_position.1 < map_width(topo_map) - 1 {
-        possible_dirs.push((0, 1));
-    }
-
     let mut trails = Vec::<Trail>::new();
 
-    for dir in possible_dirs {
-        let new_pos = (
-            (start_position.0 as isize + dir.0 as isize) as usize,
-            (start_position.1 as isize + dir.1 as isize) as usize,
-        );
-        let new_elevation = topo_map[new_pos.0][new_pos.1];
+    for new_pos in topo_map.neighbours4(*start_position) {
+        let new_elevation = topo_map[new_pos];
         if new_elevation > current_elevation && new_elevation - current_elevation == 1 {
             for mut new_trail in find_trails_from(topo_map, &new_pos) {
                 new_trail.insert(0, *start_position);
@@ -78,20 +39,71 @@ pub fn find_trails_from(topo_map: &TopoMap, start_position: &Pos) -> Vec<Trail>
 }
 
 pub fn draw_trail(topo_map: &TopoMap, trail: &Trail) {
-    let map_height = map_height(topo_map);
-    let map_width = map_width(topo_map);
-
-    let line = Vec::<char>::from_iter((0..map_width).map(|_| '.'));
-
-    let mut lines = Vec::<Vec<char>>::from_iter((0..map_height).map(|_| line.clone()));
+    let mut lines = Grid::new(topo_map.width(), topo_map.height(), '.');
 
     for (idx, pos) in trail.iter().enumerate() {
         let idx = idx as u32;
-        lines[pos.0][pos.1] = char::from_digit(idx, 10).unwrap();
+        lines[*pos] = char::from_digit(idx, 10).unwrap();
     }
 
-    for line in lines {
-        println!("{}", String::from_iter(line));
+    debug!("trail:\n{lines}");
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = TopoMap;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(topo_map: &Self::Input) -> Result<usize, SolveError> {
+        let scores = par_map(&find_start_points(topo_map), |start_position| {
+            let trails = find_trails_from(topo_map, start_position);
+            let mut end_points =
+                Vec::<Pos>::from_iter(trails.iter().map(|trail| *trail.last().unwrap()));
+            end_points.sort();
+            end_points.dedup();
+
+            end_points.len()
+        });
+
+        Ok(scores.into_iter().sum())
+    }
+
+    fn part2(topo_map: &Self::Input) -> Result<usize, SolveError> {
+        let ratings = par_map(&find_start_points(topo_map), |start_position| {
+            find_trails_from(topo_map, start_position).len()
+        });
+        Ok(ratings.into_iter().sum())
+    }
+}
+
+impl Picture for Day10 {
+    /// Every position on a hiking trail, from the trailheads in yellow up to
+    /// the summits in red.
+    fn picture(topo_map: &Self::Input) -> Frame {
+        let mut frame = Frame::from_grid(topo_map, |height| {
+            Cell::new(char::from_digit(*height as u32, 10).unwrap_or('?')).fg(Color::Gray)
+        });
+
+        for start_position in find_start_points(topo_map) {
+            for trail in find_trails_from(topo_map, &start_position) {
+                for pos in trail {
+                    let color = match topo_map[pos] {
+                        0 => Color::Yellow,
+                        9 => Color::Red,
+                        _ => Color::Green,
+                    };
+                    let cell = frame.cells()[pos];
+                    frame.set(pos.into(), cell.fg(color));
+                }
+            }
+        }
+
+        frame
     }
-    println!("");
 }
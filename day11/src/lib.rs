@@ -1,115 +1,183 @@
-use std::{collections::HashMap, u64, usize};
+use tracing::debug;
+
+use aoc_common::{checked_sum, parse_number, Memo, ParseError, Solution, SolveError};
 
 pub type Stone = u64;
 pub type StoneCollection = Vec<Stone>;
-pub type StoneCache = HashMap<(Stone, usize), usize>;
+pub type StoneCache = Memo<(Stone, usize), Result<usize, SolveError>>;
 
-pub fn parse_input(input: &str) -> StoneCollection {
+pub fn parse_input(input: &str) -> Result<StoneCollection, ParseError> {
     input
-        .trim()
         .split_ascii_whitespace()
-        .map(|item| item.parse::<Stone>().unwrap())
+        .map(|item| parse_number(input, item))
         .collect()
 }
 
 fn count_digits(num: Stone) -> u64 {
-    (num as f64).log10().floor() as u64 + 1
+    // in floating point, log10 of 10^15 - 1 and above rounds up to the next power
+    num.checked_ilog10().unwrap_or(0) as u64 + 1
+}
+
+/// The stone with odd digits turns into a 2024 times larger one, which may
+/// not fit in a `Stone`.
+fn multiply_stone(stone: Stone) -> Result<Stone, SolveError> {
+    stone.checked_mul(2024).ok_or(SolveError::Overflow)
 }
 
-fn push_stone(stone_collection: &mut StoneCollection, stone: Stone) {
+fn push_stone(stone_collection: &mut StoneCollection, stone: Stone) -> Result<(), SolveError> {
     if stone == 0 {
         stone_collection.push(1);
-        return;
+        return Ok(());
     }
 
     let digit_count = count_digits(stone) as u32;
 
-    if digit_count % 2 == 0 {
+    if digit_count.is_multiple_of(2) {
         let div = 10u64.pow(digit_count / 2);
         let left = stone / div;
         let right = stone % div;
         stone_collection.push(left);
         stone_collection.push(right);
-        return;
+        return Ok(());
     }
 
-    stone_collection.push(stone * 2024);
+    stone_collection.push(multiply_stone(stone)?);
+    Ok(())
 }
 
-pub fn blink_once(stone_collection: &StoneCollection) -> StoneCollection {
+pub fn blink_once(stone_collection: &StoneCollection) -> Result<StoneCollection, SolveError> {
     let mut new_stone_collection = StoneCollection::with_capacity(stone_collection.len() * 2);
 
     for stone in stone_collection {
-        push_stone(&mut new_stone_collection, *stone);
+        push_stone(&mut new_stone_collection, *stone)?;
     }
-    return new_stone_collection;
+    return Ok(new_stone_collection);
 }
 
-pub fn count_stones_recursively(stone: Stone, depth: usize, cache: &mut StoneCache) -> usize {
-    if cache.contains_key(&(stone, depth)) {
-        return cache.get(&(stone, depth)).unwrap().to_owned();
-    }
-
+pub fn count_stones_recursively(
+    stone: Stone,
+    depth: usize,
+    cache: &mut StoneCache,
+) -> Result<usize, SolveError> {
     if depth == 0 {
-        // print!("{stone} ");
-        return 1;
+        return Ok(1);
     }
 
-    let next_depth = depth - 1;
+    cache.cached(&(stone, depth), |cache| {
+        let next_depth = depth - 1;
 
-    if stone == 0 {
-        let result = count_stones_recursively(1, next_depth, cache);
-        cache.insert((stone, depth), result);
-        return result;
-    }
+        if stone == 0 {
+            return count_stones_recursively(1, next_depth, cache);
+        }
 
-    let result: usize;
-    let digit_count = count_digits(stone) as u32;
+        let digit_count = count_digits(stone) as u32;
 
-    if digit_count % 2 == 0 {
-        let div = 10u64.pow(digit_count / 2);
-        let left_stone = stone / div;
-        let right_stone = stone % div;
-        result = count_stones_recursively(left_stone, next_depth, cache)
-            + count_stones_recursively(right_stone, next_depth, cache);
-    } else {
-        result = count_stones_recursively(stone * 2024, next_depth, cache);
+        if digit_count.is_multiple_of(2) {
+            let div = 10u64.pow(digit_count / 2);
+            let left_stone = stone / div;
+            let right_stone = stone % div;
+            let left_count = count_stones_recursively(left_stone, next_depth, cache)?;
+            let right_count = count_stones_recursively(right_stone, next_depth, cache)?;
+            left_count
+                .checked_add(right_count)
+                .ok_or(SolveError::Overflow)
+        } else {
+            count_stones_recursively(multiply_stone(stone)?, next_depth, cache)
+        }
+    })
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = StoneCollection;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    cache.insert((stone, depth), result);
-    return result;
+    fn part1(stone_collection: &Self::Input) -> Result<usize, SolveError> {
+        let mut stone_collection = stone_collection.clone();
+        for _ in 0..25 {
+            stone_collection = blink_once(&stone_collection)?;
+        }
+        Ok(stone_collection.len())
+    }
+
+    fn part2(stone_collection: &Self::Input) -> Result<usize, SolveError> {
+        let mut cache = StoneCache::new();
+        let counts = stone_collection
+            .iter()
+            .map(|stone| count_stones_recursively(*stone, 75, &mut cache))
+            .collect::<Result<Vec<_>, _>>()?;
+        debug!(stones = cache.len(), stats = %cache.stats(), "stone cache");
+        checked_sum(counts)
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
     #[test]
     fn push_stone_test() {
         let mut stone_collection = StoneCollection::new();
         for stone in [0, 1, 10, 99, 999].iter() {
-            push_stone(&mut stone_collection, *stone);
+            push_stone(&mut stone_collection, *stone).unwrap();
         }
         assert_eq!(stone_collection, [1, 2024, 1, 0, 9, 9, 2021976]);
     }
 
+    #[test]
+    fn overflow_test() {
+        let mut stone_collection = StoneCollection::new();
+        // 15 digits, the largest odd number of them that still fits once multiplied
+        push_stone(&mut stone_collection, 999_999_999_999_999).unwrap();
+        assert_eq!(stone_collection, [2_023_999_999_999_997_976]);
+        // 20 digits are even, so the largest stone of all just splits
+        push_stone(&mut stone_collection, u64::MAX).unwrap();
+        assert_eq!(stone_collection[1..], [1844674407, 3709551615]);
+
+        assert_eq!(
+            push_stone(&mut stone_collection, 10_000_000_000_000_000),
+            Err(SolveError::Overflow)
+        );
+        assert_eq!(
+            blink_once(&vec![1, 10_000_000_000_000_000]),
+            Err(SolveError::Overflow)
+        );
+        let mut cache = StoneCache::new();
+        assert_eq!(
+            count_stones_recursively(10_000_000_000_000_000, 1, &mut cache),
+            Err(SolveError::Overflow)
+        );
+        assert_eq!(
+            Day11::part1(&vec![10_000_000_000_000_000]),
+            Err(SolveError::Overflow)
+        );
+    }
+
     #[test]
     fn recursive_test() {
         let mut cache = StoneCache::new();
 
-        assert_eq!(count_stones_recursively(1, 1, &mut cache), 1,);
+        assert_eq!(count_stones_recursively(1, 1, &mut cache), Ok(1));
         println!();
-        assert_eq!(count_stones_recursively(100, 1, &mut cache), 1);
+        assert_eq!(count_stones_recursively(100, 1, &mut cache), Ok(1));
         println!();
-        assert_eq!(count_stones_recursively(1000, 1, &mut cache), 2);
+        assert_eq!(count_stones_recursively(1000, 1, &mut cache), Ok(2));
         println!();
-        assert_eq!(count_stones_recursively(1000, 2, &mut cache), 3);
+        assert_eq!(count_stones_recursively(1000, 2, &mut cache), Ok(3));
         println!();
-        assert_eq!(count_stones_recursively(100, 2, &mut cache), 2);
+        assert_eq!(count_stones_recursively(100, 2, &mut cache), Ok(2));
         println!();
-        assert_eq!(count_stones_recursively(100, 3, &mut cache), 2);
+        assert_eq!(count_stones_recursively(100, 3, &mut cache), Ok(2));
         println!();
-        assert_eq!(count_stones_recursively(100, 4, &mut cache), 4);
+        assert_eq!(count_stones_recursively(100, 4, &mut cache), Ok(4));
         println!();
     }
 
@@ -120,7 +188,7 @@ mod tests {
 
         let mut res: usize = 0;
         for stone in stone_collection.iter() {
-            res += count_stones_recursively(*stone, 1, &mut cache);
+            res += count_stones_recursively(*stone, 1, &mut cache).unwrap();
         }
         assert_eq!(res, 7);
         println!();
@@ -128,7 +196,7 @@ mod tests {
         let stone_collection: Vec<Stone> = vec![125, 17];
         let mut res: usize = 0;
         for stone in stone_collection.iter() {
-            res += count_stones_recursively(*stone, 6, &mut cache);
+            res += count_stones_recursively(*stone, 6, &mut cache).unwrap();
         }
         assert_eq!(res, 22);
         println!()
@@ -141,5 +209,60 @@ mod tests {
         assert_eq!(count_digits(10), 2);
         assert_eq!(count_digits(99), 2);
         assert_eq!(count_digits(100), 3);
+        assert_eq!(count_digits(999_999_999_999_999), 15);
+        assert_eq!(count_digits(u64::MAX), 20);
+    }
+
+    /// Blinks the slow and obvious way, on the decimal digits of every stone.
+    fn reference_blink(stones: &[Stone]) -> Vec<Stone> {
+        let mut next = Vec::new();
+        for stone in stones {
+            let digits = stone.to_string();
+            if *stone == 0 {
+                next.push(1);
+            } else if digits.len() % 2 == 0 {
+                let (left, right) = digits.split_at(digits.len() / 2);
+                next.push(left.parse().unwrap());
+                next.push(right.parse().unwrap());
+            } else {
+                next.push(stone * 2024);
+            }
+        }
+        next
+    }
+
+    /// Stones below 10^15, so that they can be multiplied by 2024 in a u64,
+    /// and often right next to a power of ten where the digit count changes.
+    fn stone() -> impl Strategy<Value = Stone> {
+        prop_oneof![
+            0..1_000_000_000_000_000u64,
+            (1..=15u32, -2..=2i64)
+                .prop_map(|(exp, delta)| 10u64.pow(exp).saturating_add_signed(delta)),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn blink_once_matches_reference(stones in prop::collection::vec(stone(), 0..8)) {
+            prop_assert_eq!(blink_once(&stones), Ok(reference_blink(&stones)));
+        }
+
+        #[test]
+        fn count_stones_recursively_matches_reference(
+            stone in 0..1_000_000u64,
+            depth in 0..20usize,
+        ) {
+            let mut stones = vec![stone];
+            for _ in 0..depth {
+                stones = reference_blink(&stones);
+            }
+
+            let mut cache = StoneCache::new();
+            let count = count_stones_recursively(stone, depth, &mut cache);
+            prop_assert_eq!(count, Ok(stones.len()));
+            // and again from the filled cache
+            let count = count_stones_recursively(stone, depth, &mut cache);
+            prop_assert_eq!(count, Ok(stones.len()));
+        }
     }
 }
@@ -3,15 +3,17 @@ use std::{
     vec,
 };
 
-type DataType = Vec<Vec<char>>;
-type NodeIdx = (usize, usize);
-
-pub fn parse_input(input: &str) -> DataType {
-    input
-        .trim()
-        .split("\n")
-        .map(|line| line.chars().collect())
-        .collect()
+use aoc_anim::{Cell, Color, Frame, Picture};
+use aoc_common::{ParseError, Solution, SolveError};
+use aoc_grid::{Grid, Idx};
+use tracing::debug;
+
+pub type DataType = Grid<char>;
+type NodeIdx = Idx;
+type Section = (NodeIdx, NodeIdx);
+
+pub fn parse_input(input: &str) -> Result<DataType, ParseError> {
+    Grid::parse(input, |chr| chr)
 }
 
 type NodeGroup = (char, HashSet<NodeIdx>);
@@ -19,70 +21,36 @@ type GroupCollection = Vec<NodeGroup>;
 
 pub fn find_groups(data: &DataType) -> GroupCollection {
     let mut group_collection = GroupCollection::new();
-    let mut visited_nodes = HashSet::<(usize, usize)>::new();
-
-    for (line_idx, line) in data.iter().enumerate() {
-        for (column_idx, node_type) in line.iter().enumerate() {
-            let node_idx = (line_idx, column_idx);
+    let mut visited_nodes = HashSet::<NodeIdx>::new();
 
-            if visited_nodes.contains(&node_idx) {
-                continue;
-            }
-
-            let mut group = HashSet::<NodeIdx>::new();
-            group.insert(node_idx);
-            find_neighbours(data, node_idx, &mut group);
+    for (node_idx, node_type) in data.iter() {
+        if visited_nodes.contains(&node_idx) {
+            continue;
+        }
 
-            for node in group.iter() {
-                visited_nodes.insert(*node);
-            }
+        let mut group = HashSet::<NodeIdx>::new();
+        group.insert(node_idx);
+        find_neighbours(data, node_idx, &mut group);
 
-            group_collection.push((*node_type, group));
+        for node in group.iter() {
+            visited_nodes.insert(*node);
         }
+
+        group_collection.push((*node_type, group));
     }
 
     return group_collection;
 }
 
 fn get_neighbour_nodes_of_same_type(data: &DataType, node_idx: NodeIdx) -> Vec<NodeIdx> {
-    let node_type = data[node_idx.0][node_idx.1];
-    let available_positions = get_neighbour_nodes_of_all_types(data, node_idx);
+    let node_type = data[node_idx];
 
-    let mut nodes = Vec::<NodeIdx>::new();
-
-    for p in available_positions {
-        if data[p.0][p.1] == node_type {
-            nodes.push(p);
-        }
-    }
-
-    return nodes;
-}
-
-fn get_neighbour_nodes_of_all_types(data: &DataType, node_idx: NodeIdx) -> Vec<NodeIdx> {
-    let mut available_positions = Vec::<NodeIdx>::new();
-
-    if node_idx.0 > 0 {
-        available_positions.push((node_idx.0 - 1, node_idx.1));
-    }
-    if node_idx.0 < data.len() - 1 {
-        available_positions.push((node_idx.0 + 1, node_idx.1));
-    }
-    if node_idx.1 > 0 {
-        available_positions.push((node_idx.0, node_idx.1 - 1));
-    }
-    if node_idx.1 < data[node_idx.1].len() - 1 {
-        available_positions.push((node_idx.0, node_idx.1 + 1));
-    }
-
-    return available_positions;
+    data.neighbours4(node_idx)
+        .filter(|p| data[*p] == node_type)
+        .collect()
 }
 
-fn find_neighbours(
-    data: &DataType,
-    field_idx: (usize, usize),
-    seen_neighbours: &mut HashSet<NodeIdx>,
-) {
+fn find_neighbours(data: &DataType, field_idx: NodeIdx, seen_neighbours: &mut HashSet<NodeIdx>) {
     let neighbours = get_neighbour_nodes_of_same_type(data, field_idx);
 
     for neighbour in neighbours {
@@ -104,21 +72,18 @@ pub fn get_fence_cost(data: &DataType, group: &NodeGroup) -> usize {
         .sum();
     let cost = area * len;
 
-    println!(
-        "A region of {} plants with price = {} * {} = {}",
-        group.0, area, len, cost
-    );
+    debug!(plant = %group.0, area, perimeter = len, cost, "region");
     return cost;
 }
 
 pub fn get_fence_cost_with_discount(data: &DataType, group: &NodeGroup) -> usize {
     let area = group.1.len();
 
-    let mut top_fences = Vec::<(NodeIdx, NodeIdx)>::new();
-    let mut bottom_fences = Vec::<(NodeIdx, NodeIdx)>::new();
+    let mut top_fences = Vec::<Section>::new();
+    let mut bottom_fences = Vec::<Section>::new();
 
-    let mut left_fences = Vec::<(NodeIdx, NodeIdx)>::new();
-    let mut right_fences = Vec::<(NodeIdx, NodeIdx)>::new();
+    let mut left_fences = Vec::<Section>::new();
+    let mut right_fences = Vec::<Section>::new();
 
     for node in group.1.iter().cloned() {
         let neighbours = get_neighbour_nodes_of_same_type(data, node);
@@ -161,16 +126,16 @@ pub fn get_fence_cost_with_discount(data: &DataType, group: &NodeGroup) -> usize
 
     let cost = area * fence_len;
 
-    println!(
-        "A region of {} plants with price = {} * {} = {} \t\t [t: {}, b: {}, l: {}, r: {}]",
-        group.0,
+    debug!(
+        plant = %group.0,
         area,
-        fence_len,
+        sides = fence_len,
         cost,
-        top_sections_count,
-        bottom_sections_count,
-        left_sections_count,
-        right_sections_count
+        top = top_sections_count,
+        bottom = bottom_sections_count,
+        left = left_sections_count,
+        right = right_sections_count,
+        "region"
     );
     return cost;
 }
@@ -178,13 +143,7 @@ pub fn get_fence_cost_with_discount(data: &DataType, group: &NodeGroup) -> usize
 fn reduce_sections_1d(sections: &Vec<(usize, usize)>) -> Vec<(usize, usize)> {
     let mut sections = sections.clone();
 
-    sections.sort_by(|(left_from, left_to), (right_from, right_to)| {
-        if left_from == right_from {
-            left_to.cmp(right_to)
-        } else {
-            left_to.cmp(right_to)
-        }
-    });
+    sections.sort_by_key(|(_, to)| *to);
 
     'outer: loop {
         for i in 0..sections.len() - 1 {
@@ -202,7 +161,7 @@ fn reduce_sections_1d(sections: &Vec<(usize, usize)>) -> Vec<(usize, usize)> {
     return sections;
 }
 
-fn reduce_sections_horizontal(sections: &Vec<((usize, usize), (usize, usize))>) -> usize {
+fn reduce_sections_horizontal(sections: &Vec<Section>) -> usize {
     let mut one_d_section_map = HashMap::<usize, Vec<(usize, usize)>>::new();
 
     let only_horizontal_sections_iter = sections
@@ -226,7 +185,7 @@ fn reduce_sections_horizontal(sections: &Vec<((usize, usize), (usize, usize))>)
     reduced_1d_map.values().map(|item| item.len()).sum()
 }
 
-fn reduce_sections_vertical(sections: &Vec<((usize, usize), (usize, usize))>) -> usize {
+fn reduce_sections_vertical(sections: &Vec<Section>) -> usize {
     let mut one_d_section_map = HashMap::<usize, Vec<(usize, usize)>>::new();
 
     let only_vertical_sections_iter = sections
@@ -250,6 +209,66 @@ fn reduce_sections_vertical(sections: &Vec<((usize, usize), (usize, usize))>) ->
     reduced_1d_map.values().map(|item| item.len()).sum()
 }
 
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = DataType;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(data: &Self::Input) -> Result<usize, SolveError> {
+        Ok(find_groups(data)
+            .iter()
+            .map(|group| get_fence_cost(data, group))
+            .sum())
+    }
+
+    fn part2(data: &Self::Input) -> Result<usize, SolveError> {
+        Ok(find_groups(data)
+            .iter()
+            .map(|group| get_fence_cost_with_discount(data, group))
+            .sum())
+    }
+}
+
+impl Picture for Day12 {
+    /// Every region in a color of its own, as far as the neighbouring
+    /// regions leave one.
+    fn picture(data: &Self::Input) -> Frame {
+        const COLORS: [Color; 6] = [
+            Color::Red,
+            Color::Green,
+            Color::Yellow,
+            Color::Blue,
+            Color::Magenta,
+            Color::Cyan,
+        ];
+
+        let mut frame = Frame::from_grid(data, |plant| Cell::new(*plant));
+        for (plant, group) in find_groups(data) {
+            let taken: HashSet<Color> = group
+                .iter()
+                .flat_map(|node_idx| data.neighbours4(*node_idx))
+                .map(|node_idx| frame.cells()[node_idx].bg)
+                .collect();
+            let color = COLORS
+                .into_iter()
+                .find(|color| !taken.contains(color))
+                .unwrap_or(COLORS[0]);
+
+            for node_idx in group {
+                frame.set(node_idx.into(), Cell::new(plant).fg(Color::Black).bg(color));
+            }
+        }
+
+        frame
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -277,7 +296,7 @@ mod tests {
 
     #[test]
     fn e2e_1() {
-        let data = parse_input("EEEEE\nEXXXX\nEEEEE\nEXXXX\nEEEEE");
+        let data = parse_input("EEEEE\nEXXXX\nEEEEE\nEXXXX\nEEEEE").unwrap();
         let groups = find_groups(&data);
         let result: usize = groups
             .iter()
@@ -288,7 +307,7 @@ mod tests {
 
     #[test]
     fn e2e_2() {
-        let data = parse_input("AAAAAA\nAAABBA\nAAABBA\nABBAAA\nABBAAA\nAAAAAA");
+        let data = parse_input("AAAAAA\nAAABBA\nAAABBA\nABBAAA\nABBAAA\nAAAAAA").unwrap();
         let groups = find_groups(&data);
         let result: usize = groups
             .iter()
@@ -296,4 +315,23 @@ mod tests {
             .sum();
         assert_eq!(result, 368);
     }
+
+    #[test]
+    fn picture_test() {
+        let data = parse_input("AAAA\nBBCD\nBBCC\nEEEC").unwrap();
+        let picture = Day12::picture(&data);
+        assert_eq!(picture.to_string(), "AAAA\nBBCD\nBBCC\nEEEC");
+
+        // neighbouring regions differ in color
+        let groups = find_groups(&data);
+        for (idx, plant) in data.iter() {
+            for neighbour in data.neighbours4(idx) {
+                let same_region = groups
+                    .iter()
+                    .any(|(_, group)| group.contains(&idx) && group.contains(&neighbour));
+                let same_color = picture.cells()[idx].bg == picture.cells()[neighbour].bg;
+                assert_eq!(same_region, same_color, "{plant} at {idx:?}");
+            }
+        }
+    }
 }
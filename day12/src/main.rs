@@ -1,20 +1,7 @@
-use std::fs;
+use std::process::ExitCode;
 
-use day12::*;
+use day12::Day12;
 
-fn main() {
-    let input_path = "input/input.txt";
-    let input_path = "input/test_input.txt";
-
-    let input = fs::read_to_string(input_path).unwrap();
-    
-    let data = parse_input(&input);
-
-    let groups = find_groups(&data);
-
-    let result: usize = groups.iter().map(|group| get_fence_cost(&data, group)).sum();
-    println!("Result: {result}");
-    
-    let result: usize = groups.iter().map(|group| get_fence_cost_with_discount(&data, group)).sum();
-    println!("Result: {result}");
+fn main() -> ExitCode {
+    aoc_common::run_main_rendered::<Day12>(12, aoc_anim::save_picture::<Day12>)
 }
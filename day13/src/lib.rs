@@ -1,90 +1,370 @@
-use regex::Regex;
+use tracing::trace;
 
-#[derive(Clone, Copy)]
+use aoc_common::{checked_sum, par_map, ParseError, Solution, SolveError};
+use aoc_parse::{ints_exact, key_value, split_sections};
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Machine{
     pub button_a: (u64, u64),
     pub button_b: (u64, u64),
     pub prize: (u64, u64)
 }
 
-fn parse_machine(input: &str) -> Machine {
-    let re = Regex::new(r"^(?:Button A: X\+(\d+), Y\+(\d+))\n(?:Button B: X\+(\d+), Y\+(\d+))\n(?:Prize: X=(\d+), Y=(\d+))$").unwrap();
-
-    let caps = re.captures(input).unwrap();
-
-    Machine{
-        button_a: (
-            caps[1].parse::<u64>().unwrap(),
-            caps[2].parse::<u64>().unwrap(),
-        ),
-        button_b: (
-            caps[3].parse::<u64>().unwrap(),
-            caps[4].parse::<u64>().unwrap(),
-        ),
-        prize: (
-            caps[5].parse::<u64>().unwrap(),
-            caps[6].parse::<u64>().unwrap(),
-        )
+fn parse_machine(input: &str) -> Result<Machine, ParseError> {
+    let line_formats = [
+        ("Button A:", "`Button A: X+<n>, Y+<n>`"),
+        ("Button B:", "`Button B: X+<n>, Y+<n>`"),
+        ("Prize:", "`Prize: X=<n>, Y=<n>`"),
+    ];
+
+    let mut lines = input.lines().map(str::trim);
+    let mut values = [(0, 0); 3];
+
+    for (value, (prefix, expected)) in values.iter_mut().zip(line_formats) {
+        let line = lines.next().ok_or_else(|| ParseError::at_end(input, expected))?;
+        let fields = line.strip_prefix(prefix).ok_or_else(|| ParseError::at(input, line, expected))?;
+
+        let numbers = match prefix {
+            "Prize:" => key_value(fields, "X").and_then(|x| Ok([x, key_value(fields, "Y")?])),
+            _ => ints_exact(fields),
+        };
+        let [x, y] = numbers.map_err(|err| err.within(input, fields))?;
+        *value = (x, y);
     }
+
+    Ok(Machine{
+        button_a: values[0],
+        button_b: values[1],
+        prize: values[2],
+    })
 }
 
-pub fn parse_input(input: &str) -> Vec<Machine> {
-    input
-        .trim()
-        .split("\n\n")
-        .map(parse_machine)
+pub fn parse_input(input: &str) -> Result<Vec<Machine>, ParseError> {
+    split_sections(input)
+        .map(|section| parse_machine(section).map_err(|err| err.within(input, section)))
         .collect()
 }
 
-pub fn calc_winning_cost(machine: &Machine) -> Option<u64> {
-    let mut target_x = machine.prize.0 as f64;
-    let mut target_y = machine.prize.1 as f64;
-    let mut a_x = machine.button_a.0 as f64;
-    let mut a_y = machine.button_a.1 as f64;
-    let mut b_x = machine.button_b.0 as f64;
-    let mut b_y = machine.button_b.1 as f64;
+/// Solves the two equations of the claw exactly, by Cramer's rule in
+/// `i128`, and returns the cheapest number of times to press `A` and `B`.
+/// Prizes far enough away for the products to overflow even that are an
+/// error.
+pub fn winning_presses(machine: &Machine) -> Result<Option<(u64, u64)>, SolveError> {
+    let [a_x, a_y, b_x, b_y, target_x, target_y] = [
+        machine.button_a.0,
+        machine.button_a.1,
+        machine.button_b.0,
+        machine.button_b.1,
+        machine.prize.0,
+        machine.prize.1,
+    ]
+    .map(i128::from);
 
-    let mul_1 = a_x;
-    let mul_2 = a_y;
+    let cross = |p: i128, q: i128, r: i128, s: i128| -> Result<i128, SolveError> {
+        let left = p.checked_mul(q).ok_or(SolveError::Overflow)?;
+        let right = r.checked_mul(s).ok_or(SolveError::Overflow)?;
+        left.checked_sub(right).ok_or(SolveError::Overflow)
+    };
 
-    target_x *= mul_2;
-    a_x *= mul_2;
-    b_x *= mul_2;
+    let det = cross(a_x, b_y, a_y, b_x)?;
+    if det == 0 {
+        trace!(?machine, "the buttons move the claw in the same direction");
+        // both buttons and the prize lie on one line, if the prize can be won at all
+        let (dir_x, dir_y) = if (a_x, a_y) != (0, 0) { (a_x, a_y) } else { (b_x, b_y) };
+        if (dir_x, dir_y) == (0, 0) {
+            // neither button moves the claw
+            return Ok(((target_x, target_y) == (0, 0)).then_some((0, 0)));
+        }
+        if cross(dir_x, target_y, dir_y, target_x)? != 0 {
+            return Ok(None);
+        }
+        // so one coordinate tells it all, unless the line runs along the other axis
+        return Ok(match dir_x {
+            0 => line_presses(machine.button_a.1, machine.button_b.1, machine.prize.1),
+            _ => line_presses(machine.button_a.0, machine.button_b.0, machine.prize.0),
+        });
+    }
+    let a_det = cross(target_x, b_y, target_y, b_x)?;
+    let b_det = cross(a_x, target_y, a_y, target_x)?;
 
-    target_y *= mul_1;
-    a_y *= mul_1;
-    b_y *= mul_1;
+    if a_det % det != 0 || b_det % det != 0 || a_det / det < 0 || b_det / det < 0 {
+        trace!(?machine, "the prize cannot be won");
+        return Ok(None);
+    }
 
-    assert_eq!(a_x, a_y);
+    let a = u64::try_from(a_det / det).map_err(|_| SolveError::Overflow)?;
+    let b = u64::try_from(b_det / det).map_err(|_| SolveError::Overflow)?;
+    return Ok(Some((a, b)));
+}
 
-    let target = (target_x - target_y).abs();
-    let b = target / (b_x-b_y).abs();
-    let a = (b_x * b - target_x).abs() / a_x;
+/// The cheapest `a` and `b` with `a * step_a + b * step_b == target`, for
+/// buttons that move the claw along the same line. Of all the ways to get
+/// there, the cheapest presses the button that goes further per token as
+/// often as it can.
+fn line_presses(step_a: u64, step_b: u64, target: u64) -> Option<(u64, u64)> {
+    let (step_a, step_b, target) = (u128::from(step_a), u128::from(step_b), u128::from(target));
+    if step_a == 0 || step_b == 0 {
+        // the other button has to do it alone
+        return match (step_a, step_b) {
+            (0, 0) => (target == 0).then_some((0, 0)),
+            (0, _) => (target % step_b == 0).then(|| (0, (target / step_b) as u64)),
+            _ => (target % step_a == 0).then(|| ((target / step_a) as u64, 0)),
+        };
+    }
 
-    if a.fract() != 0.0 || b.fract() != 0.0{
-        println!("No solution");
+    let gcd = gcd(step_a, step_b);
+    if target % gcd != 0 {
+        return None;
+    }
+    // the numbers of `A` presses that leave a multiple of `step_b` come
+    // `period` apart, starting at `first`
+    let period = step_b / gcd;
+    let first = target / gcd % period * inverse(step_a / gcd % period, period) % period;
+    if first * step_a > target {
         return None;
     }
 
+    // `A` costs three tokens to `B`'s one
+    let a = if step_a > 3 * step_b {
+        first + (target / step_a - first) / period * period
+    } else {
+        first
+    };
+    Some((a as u64, ((target - a * step_a) / step_b) as u64))
+}
+
+fn gcd(a: u128, b: u128) -> u128 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+/// `x` with `value * x` one more than a multiple of `modulus`, for `value`
+/// and `modulus` without common divisors.
+fn inverse(value: u128, modulus: u128) -> u128 {
+    // extended Euclid, keeping the coefficients of `value` modulo `modulus`
+    let (mut r0, mut r1) = (modulus, value);
+    let (mut t0, mut t1) = (0, 1 % modulus);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (t0, t1) = (t1, (t0 + modulus - q % modulus * t1 % modulus) % modulus);
+    }
+    t0
+}
+
+/// The tokens needed to win the prize, if it can be won at all. A cost
+/// that does not fit in a `u64` is an error.
+pub fn calc_winning_cost(machine: &Machine) -> Result<Option<u64>, SolveError> {
+    let Some((a, b)) = winning_presses(machine)? else {
+        return Ok(None);
+    };
+
     let cost_a = 3;
     let cost_b = 1;
-    let a = a.round() as u64;
-    let b = b.round() as u64;
+    let cost = a
+        .checked_mul(cost_a)
+        .and_then(|cost| cost.checked_add(b * cost_b))
+        .ok_or(SolveError::Overflow)?;
 
-    let cost = cost_a * a + cost_b * b;
+    trace!(a, b, cost, "the prize can be won");
 
-    println!("a: {}, b: {}, cost: {}", a, b, cost);
+    return Ok(Some(cost));
+}
 
-    return Some(cost);
+fn total_cost(machines: &[Machine]) -> Result<u64, SolveError> {
+    let costs = par_map(machines, calc_winning_cost)
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()?;
+    checked_sum(costs.into_iter().flatten())
 }
 
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Vec<Machine>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(machines: &Self::Input) -> Result<u64, SolveError> {
+        total_cost(machines)
+    }
+
+    fn part2(machines: &Self::Input) -> Result<u64, SolveError> {
+        let corrected_machines: Vec<Machine> = machines
+            .iter()
+            .map(|machine| {
+                let correct = |coord: u64| {
+                    coord.checked_add(10000000000000).ok_or(SolveError::Overflow)
+                };
+                let mut m = machine.to_owned();
+                m.prize = (correct(machine.prize.0)?, correct(machine.prize.1)?);
+                Ok(m)
+            })
+            .collect::<Result<_, SolveError>>()?;
+
+        total_cost(&corrected_machines)
+    }
+}
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
     #[test]
-    fn it_works() {
-        assert_eq!(1,1);
+    fn winning_cost_test() {
+        let machine = Machine {
+            button_a: (94, 34),
+            button_b: (22, 67),
+            prize: (8400, 5400),
+        };
+        assert_eq!(calc_winning_cost(&machine), Ok(Some(280)));
+
+        let machine = Machine {
+            button_a: (26, 66),
+            button_b: (67, 21),
+            prize: (12748, 12176),
+        };
+        assert_eq!(calc_winning_cost(&machine), Ok(None));
+    }
+
+    #[test]
+    fn same_direction_test() {
+        let machine = |button_a, button_b, prize| Machine { button_a, button_b, prize };
+        // `B` goes as far as `A` for a third of the price
+        assert_eq!(calc_winning_cost(&machine((1, 1), (2, 2), (4, 4))), Ok(Some(2)));
+        assert_eq!(calc_winning_cost(&machine((1, 1), (2, 2), (5, 5))), Ok(Some(5)));
+        // `A` goes four times as far for three times the price
+        assert_eq!(calc_winning_cost(&machine((4, 4), (1, 1), (9, 9))), Ok(Some(7)));
+        assert_eq!(calc_winning_cost(&machine((2, 2), (4, 4), (5, 5))), Ok(None));
+        assert_eq!(calc_winning_cost(&machine((1, 1), (2, 2), (4, 5))), Ok(None));
+
+        let far = 10_000_000_000_001;
+        let cost = calc_winning_cost(&machine((1, 1), (2, 2), (far, far)));
+        assert_eq!(cost, Ok(Some(3 + far / 2)));
+
+        // buttons that do not move the claw at all, or only one of them
+        assert_eq!(calc_winning_cost(&machine((0, 0), (0, 0), (0, 0))), Ok(Some(0)));
+        assert_eq!(calc_winning_cost(&machine((0, 0), (0, 0), (1, 0))), Ok(None));
+        assert_eq!(calc_winning_cost(&machine((0, 0), (0, 3), (0, 9))), Ok(Some(3)));
+        assert_eq!(calc_winning_cost(&machine((0, 2), (0, 0), (0, 9))), Ok(None));
+    }
+
+    #[test]
+    fn precision_test() {
+        // far beyond the 53 bits of an f64, where the old float version lost
+        // the last digits
+        let machine = Machine {
+            button_a: (1, 2),
+            button_b: (3, 1),
+            prize: (10_000_000_000_000_010, 20_000_000_000_000_005),
+        };
+        assert_eq!(calc_winning_cost(&machine), Ok(Some(30_000_000_000_000_006)));
+        let machine = Machine {
+            prize: (10_000_000_000_000_011, 20_000_000_000_000_005),
+            ..machine
+        };
+        assert_eq!(calc_winning_cost(&machine), Ok(None));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn overflow_test() {
+        // u64::MAX / 3 presses of `A` cost exactly u64::MAX
+        let machine = Machine {
+            button_a: (3, 3),
+            button_b: (1, 2),
+            prize: (u64::MAX / 3 * 3, u64::MAX / 3 * 3),
+        };
+        assert_eq!(calc_winning_cost(&machine), Ok(Some(u64::MAX / 3 * 3)));
+        let machine = Machine { button_a: (1, 1), prize: (u64::MAX, u64::MAX), ..machine };
+        assert_eq!(calc_winning_cost(&machine), Err(SolveError::Overflow));
+        let machine = Machine { button_a: (u64::MAX, 1), button_b: (1, u64::MAX), ..machine };
+        assert_eq!(calc_winning_cost(&machine), Err(SolveError::Overflow));
+
+        let machine = Machine { button_a: (1, 1), button_b: (1, 2), prize: (u64::MAX, 0) };
+        assert_eq!(Day13::part2(&vec![machine]), Err(SolveError::Overflow));
+    }
+
+    /// Tries every number of `A` presses that does not overshoot the prize.
+    fn reference_winning_cost(machine: &Machine) -> Option<u64> {
+        let ((ax, ay), (bx, by), (px, py)) = (machine.button_a, machine.button_b, machine.prize);
+        (0..=px / ax)
+            .filter_map(|a| {
+                let rest = px - a * ax;
+                let b = rest / bx;
+                (rest % bx == 0 && a * ay + b * by == py).then_some(3 * a + b)
+            })
+            .min()
+    }
+
+    /// Buttons that do not move the claw in the same direction, in front of
+    /// a prize that can be won about half of the time.
+    fn machine() -> impl Strategy<Value = Machine> {
+        let button = (1..100u64, 1..100u64);
+        (button.clone(), button, 0..100u64, 0..100u64, 0..20_000u64, 0..20_000u64, any::<bool>())
+            .prop_filter("the buttons move in the same direction", |(a, b, ..)| {
+                a.0 * b.1 != a.1 * b.0
+            })
+            .prop_map(|(button_a, button_b, a, b, x, y, winnable)| {
+                let prize = match winnable {
+                    true => (a * button_a.0 + b * button_b.0, a * button_a.1 + b * button_b.1),
+                    false => (x, y),
+                };
+                Machine { button_a, button_b, prize }
+            })
+    }
+
+    /// Buttons moving the claw in the same direction, either of them going
+    /// further, in front of a prize on their line.
+    fn same_direction_machine() -> impl Strategy<Value = Machine> {
+        ((1..25u64, 1..25u64), 1..6u64, 1..6u64, 0..1000u64).prop_map(|((x, y), k_a, k_b, n)| {
+            let (button_a, button_b) = ((x * k_a, y * k_a), (x * k_b, y * k_b));
+            Machine { button_a, button_b, prize: (x * n, y * n) }
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn winning_cost_matches_reference(machine in machine()) {
+            prop_assert_eq!(calc_winning_cost(&machine), Ok(reference_winning_cost(&machine)));
+        }
+
+        #[test]
+        fn same_direction_cost_matches_reference(machine in same_direction_machine()) {
+            prop_assert_eq!(calc_winning_cost(&machine), Ok(reference_winning_cost(&machine)));
+        }
+
+        #[test]
+        fn far_winning_cost_matches_presses(
+            (button_a, button_b) in machine().prop_map(|m| (m.button_a, m.button_b)),
+            a in 10_000_000_000..1_000_000_000_000u64,
+            b in 10_000_000_000..1_000_000_000_000u64,
+        ) {
+            // the buttons are independent, so these are the only presses
+            let prize = (a * button_a.0 + b * button_b.0, a * button_a.1 + b * button_b.1);
+            let machine = Machine { button_a, button_b, prize };
+            prop_assert_eq!(calc_winning_cost(&machine), Ok(Some(3 * a + b)));
+        }
+
+        #[test]
+        fn far_winning_presses_reach_prize(
+            mut machine in prop_oneof![machine(), same_direction_machine()],
+        ) {
+            machine.prize.0 += 10000000000000;
+            machine.prize.1 += 10000000000000;
+            if let Some((a, b)) = winning_presses(&machine).unwrap() {
+                let reach = |button: u64, other: u64| {
+                    u128::from(a) * u128::from(button) + u128::from(b) * u128::from(other)
+                };
+                let (button_a, button_b) = (machine.button_a, machine.button_b);
+                prop_assert_eq!(reach(button_a.0, button_b.0), u128::from(machine.prize.0));
+                prop_assert_eq!(reach(button_a.1, button_b.1), u128::from(machine.prize.1));
+            }
+        }
+    }
+}
@@ -1,6 +1,12 @@
 use std::collections::HashMap;
-use regex::Regex;
 
+use aoc_anim::{Animate, Cell, Color, Frame, Picture, Render};
+use aoc_common::{Param, Params, ParseError, Simulation, Solution, SolveError};
+use aoc_grid::Grid;
+use aoc_parse::{ints_exact, value};
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Robot {
     pub p_x: u32,
     pub p_y: u32,
@@ -9,46 +15,37 @@ pub struct Robot {
 }
 
 impl Robot {
+    /// Moves the robot `dt` seconds ahead, wrapping around the edges. A
+    /// robot that starts outside the map comes in on the other side.
     pub fn step(&mut self, dt: u32, map_width: u32, map_height: u32) {
-        self.p_x = (self.p_x as i64 + self.v_x * dt as i64).rem_euclid(map_width as i64) as u32;
-        self.p_y = (self.p_y as i64 + self.v_y * dt as i64).rem_euclid(map_height as i64) as u32;
+        // a velocity times the seconds always fits in an `i128`
+        let advance = |p: u32, v: i64, size: u32| {
+            (i128::from(p) + i128::from(v) * i128::from(dt)).rem_euclid(i128::from(size)) as u32
+        };
+        self.p_x = advance(self.p_x, self.v_x, map_width);
+        self.p_y = advance(self.p_y, self.v_y, map_height);
     }
 }
 
-pub fn parse_input(input: &str) -> Vec<Robot> {
-    input.trim().split('\n').map(parse_line).collect()
-}
-
-fn parse_line(input: &str) -> Robot {
-    let rg = Regex::new(r"(?:^p=(\d+,\d+) v=(-?\d+,-?\d+)$)").unwrap();
-    let captures = rg.captures(input).unwrap();
-
-    Robot {
-        p_x: captures[1]
-            .split_once(',')
-            .unwrap()
-            .0
-            .parse::<u32>()
-            .unwrap(),
-        p_y: captures[1]
-            .split_once(',')
-            .unwrap()
-            .1
-            .parse::<u32>()
-            .unwrap(),
-        v_x: captures[2]
-            .split_once(',')
-            .unwrap()
-            .0
-            .parse::<i64>()
-            .unwrap(),
-        v_y: captures[2]
-            .split_once(',')
-            .unwrap()
-            .1
-            .parse::<i64>()
-            .unwrap(),
-    }
+pub fn parse_input(input: &str) -> Result<Vec<Robot>, ParseError> {
+    input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| parse_line(line).map_err(|err| err.within(input, line)))
+        .collect()
+}
+
+fn parse_line(input: &str) -> Result<Robot, ParseError> {
+    let line = input.trim();
+    let field = |key: &str| {
+        value(line, key).map_err(|_| ParseError::at(input, line, "`p=<x>,<y> v=<dx>,<dy>`"))
+    };
+    let (position, velocity) = (field("p")?, field("v")?);
+
+    let [p_x, p_y] = ints_exact(position).map_err(|err| err.within(input, position))?;
+    let [v_x, v_y] = ints_exact(velocity).map_err(|err| err.within(input, velocity))?;
+
+    Ok(Robot { p_x, p_y, v_x, v_y })
 }
 
 pub fn calc_safety_factor(robots: &[Robot], field_width: u32, field_height: u32) -> u32 {
@@ -92,7 +89,7 @@ pub fn could_be_christmas_tree_1(robots: &[Robot], map_width: u32, _: u32) -> bo
 }
 
 pub fn could_be_christmas_tree_2(robots: &[Robot], _: u32, map_height: u32) -> bool {
-    let mut map = HashMap::<u32, usize>::from_iter((0..map_height).map(|i| (i as u32, 0)));
+    let mut map = HashMap::<u32, usize>::from_iter((0..map_height).map(|i| (i, 0)));
 
     for robot in robots {
         *map.get_mut(&robot.p_y).unwrap() += 1;
@@ -110,29 +107,150 @@ pub fn could_be_christmas_tree_3(robots: &[Robot]) -> bool {
     return coords_vec.len() == robots.len();
 }
 
-pub fn draw_robots(robots: &[Robot], field_width: u32, field_height: u32) {
-    let mut field = Vec::<Vec<u8>>::new();
-    let line: Vec<u8> = (0..field_width).map(|_| 0).collect();
+/// The field the first time after the start that the robots could be
+/// showing a Christmas tree, if they ever do. A field with more tiles than
+/// a `u32` counts is an error.
+pub fn find_christmas_tree(field: &Field) -> Result<Option<Field>, SolveError> {
+    // robot positions repeat after width * height steps
+    let period = field.width.checked_mul(field.height).ok_or(SolveError::Overflow)?;
+    let mut field = field.clone();
+
+    field.step();
+    field.run_until(|field| field.seconds >= period || could_be_christmas_tree_3(&field.robots));
+
+    Ok(could_be_christmas_tree_3(&field.robots).then_some(field))
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Field {
+    pub robots: Vec<Robot>,
+    pub width: u32,
+    pub height: u32,
+    /// Seconds the robots have been moving for.
+    pub seconds: u32,
+}
+
+impl Simulation for Field {
+    type State = Vec<(u32, u32)>;
 
-    for _ in 0..field_height {
-        field.push(line.clone());
+    fn state(&self) -> Self::State {
+        self.robots.iter().map(|robot| (robot.p_x, robot.p_y)).collect()
     }
 
-    for robot in robots {
-        field[robot.p_y as usize][robot.p_x as usize] += 1;
+    /// One second; the robots never stop.
+    fn step(&mut self) -> bool {
+        for robot in self.robots.iter_mut() {
+            robot.step(1, self.width, self.height);
+        }
+        self.seconds += 1;
+        true
     }
+}
+
+impl Render for Field {
+    /// Number of robots on every tile, `.` where there are none.
+    fn render(&self) -> Frame {
+        let mut counts = Grid::new(self.width as usize, self.height as usize, 0);
+        for robot in &self.robots {
+            counts[(robot.p_y as usize, robot.p_x as usize)] += 1;
+        }
 
-    for line in field {
-        let str = String::from_iter(line.iter().map(|item| match item {
-            0 => '.',
-            i => char::from_digit(*i as u32, 10).unwrap(),
-        }));
-        println!("{}", str);
+        Frame::from_grid(&counts, |count| match count {
+            0 => Cell::new('.').fg(Color::Gray),
+            count => Cell::new(char::from_digit(*count, 10).unwrap_or('*')).fg(Color::Green),
+        })
+    }
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = Field;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "width",
+            help: "Width of the space the robots move in",
+            default: 101,
+            max: u32::MAX as u64,
+        },
+        Param {
+            name: "height",
+            help: "Height of the space the robots move in",
+            default: 103,
+            max: u32::MAX as u64,
+        },
+    ];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let mut field = Field {
+            robots: parse_input(input)?,
+            width: 0,
+            height: 0,
+            seconds: 0,
+        };
+        Self::configure(&mut field, &Params::new(Self::PARAMS));
+        Ok(field)
+    }
+
+    fn configure(field: &mut Self::Input, params: &Params) {
+        let size = |name| u32::try_from(params.get(name)).unwrap_or(u32::MAX);
+        field.width = size("width");
+        field.height = size("height");
+        // robots placed outside a field smaller than the input wrap around it
+        for robot in field.robots.iter_mut() {
+            robot.step(0, field.width, field.height);
+        }
+    }
+
+    fn part1(field: &Self::Input) -> Result<u32, SolveError> {
+        let mut field = field.clone();
+        field.run_for(100);
+        Ok(calc_safety_factor(&field.robots, field.width, field.height))
+    }
+
+    fn part2(field: &Self::Input) -> Result<u32, SolveError> {
+        let tree = find_christmas_tree(field)?.ok_or(SolveError::NoSolution)?;
+        Ok(tree.seconds)
+    }
+}
+
+impl Animate for Day14 {
+    fn frames(field: &Self::Input) -> impl Iterator<Item = Frame> + '_ {
+        let mut field = field.clone();
+
+        // robot positions repeat after width * height steps
+        (0..u64::from(field.width) * u64::from(field.height)).map(move |second| {
+            if second > 0 {
+                field.step();
+            }
+
+            let caption = if could_be_christmas_tree_3(&field.robots) {
+                format!("after {} seconds, no two robots share a tile", field.seconds)
+            } else {
+                format!("after {} seconds", field.seconds)
+            };
+            field.render().with_caption(caption)
+        })
+    }
+}
+
+impl Picture for Day14 {
+    /// The robots showing the Christmas tree, or where they start if they
+    /// never do.
+    fn picture(field: &Self::Input) -> Frame {
+        let tree = find_christmas_tree(field).ok().flatten();
+        tree.unwrap_or_else(|| field.clone()).render()
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use aoc_common::Cycle;
+
     use super::*;
 
     #[test]
@@ -158,4 +276,43 @@ mod tests {
         assert_eq!(robot.p_x, 1);
         assert_eq!(robot.p_y, 3);
     }
+
+    #[test]
+    fn field_size_test() {
+        let input = "p=10,6 v=1,1\np=3,0 v=-1,0\n";
+        let mut field = Day14::parse(input).unwrap();
+        let mut params = Params::new(Day14::PARAMS);
+        params.assign("width=4").unwrap();
+        params.assign("height=5").unwrap();
+        Day14::configure(&mut field, &params);
+        assert_eq!(field.state(), vec![(2, 1), (3, 0)]);
+        assert_eq!(field.render().to_string(), "...1\n..1.\n....\n....\n....");
+
+        let robot = Robot { p_x: 0, p_y: 0, v_x: i64::MIN, v_y: i64::MAX };
+        let mut far = Field { robots: vec![robot], width: 3, height: 5, seconds: 0 };
+        far.robots[0].step(u32::MAX, far.width, far.height);
+        assert_eq!(far.state(), vec![(0, 0)]);
+
+        assert!(Day14::parse("p=-1,2 v=1,1").is_err());
+
+        // more tiles than seconds a `u32` can count
+        params.assign("width=100000").unwrap();
+        params.assign("height=100000").unwrap();
+        Day14::configure(&mut field, &params);
+        assert_eq!(Day14::part2(&field), Err(SolveError::Overflow));
+    }
+
+    #[test]
+    fn cycle_test() {
+        let mut field = Day14::parse(include_str!("../input/test_input.txt")).unwrap();
+        (field.width, field.height) = (11, 7);
+        let start = field.state();
+
+        let cycle = field.find_cycle().unwrap();
+        assert_eq!(cycle, Cycle { start: 0, period: 77 });
+
+        field.run_for(cycle.period);
+        assert_eq!(field.seconds, 77);
+        assert_eq!(field.state(), start);
+    }
 }
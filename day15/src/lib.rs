@@ -1,35 +1,11 @@
-#[derive(Clone, Copy)]
-pub enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-impl Direction {
-    fn from_char(chr: char) -> Direction {
-        match chr {
-            '^' => Direction::Up,
-            'v' => Direction::Down,
-            '<' => Direction::Left,
-            '>' => Direction::Right,
-            _ => panic!(),
-        }
-    }
-
-    fn to_vector(&self) -> (i64, i64) {
-        match self {
-            Direction::Up => (-1, 0),
-            Direction::Down => (1, 0),
-            Direction::Left => (0, -1),
-            Direction::Right => (0, 1),
-        }
-    }
-}
+use aoc_anim::{Animate, Cell, Color, Frame, Inspect, Picture, Render, StepThrough};
+use aoc_common::{ParseError, Simulation, Solution, SolveError};
+use aoc_grid::{Direction, Grid, Pos};
 
-type DirectionList = Vec<Direction>;
+pub type DirectionList = Vec<Direction>;
 
 #[derive(PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum WarehouseItem {
     Nothing,
     Box,
@@ -38,17 +14,17 @@ pub enum WarehouseItem {
 }
 
 impl WarehouseItem {
-    fn from_char(chr: char) -> WarehouseItem {
+    fn from_char(chr: char) -> Option<WarehouseItem> {
         match chr {
-            '.' => WarehouseItem::Nothing,
-            'O' => WarehouseItem::Box,
-            '#' => WarehouseItem::Wall,
-            '@' => WarehouseItem::Nothing,
-            _ => panic!(),
+            '.' => Some(WarehouseItem::Nothing),
+            'O' => Some(WarehouseItem::Box),
+            '#' => Some(WarehouseItem::Wall),
+            '@' => Some(WarehouseItem::Nothing),
+            _ => None,
         }
     }
 
-    fn to_char(&self) -> char {
+    fn to_char(self) -> char {
         match self {
             WarehouseItem::Nothing => '.',
             WarehouseItem::Box => 'O',
@@ -58,7 +34,7 @@ impl WarehouseItem {
     }
 }
 
-pub type WarehouseMap = Vec<Vec<WarehouseItem>>;
+pub type WarehouseMap = Grid<WarehouseItem>;
 
 impl std::fmt::Display for WarehouseItem {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -66,64 +42,55 @@ impl std::fmt::Display for WarehouseItem {
     }
 }
 
-#[derive(Clone, Copy)]
-pub struct WarehousePos {
-    pub pos_y: usize,
-    pub pos_x: usize,
+/// The warehouse with the robot in it.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Warehouse {
+    pub map: WarehouseMap,
+    pub robot: Pos,
 }
 
-impl WarehousePos {
-    pub fn next_pos(&self, dir: Direction) -> WarehousePos {
-        let (d_y, d_x) = dir.to_vector();
-        WarehousePos {
-            pos_y: ((self.pos_y as i64) + d_y) as usize,
-            pos_x: ((self.pos_x as i64) + d_x) as usize,
-        }
-    }
-}
-
-pub fn draw_warehouse_map(warehouse_map: &WarehouseMap, robot: Option<WarehousePos>) {
-    //let output = Vec::<String>::new();
-
-    for (line_idx, line) in warehouse_map.iter().enumerate() {
-        let mut line = String::from_iter(line.iter().map(WarehouseItem::to_char));
-
-        if let Some(robot) = robot {
-            if robot.pos_y == line_idx {
-                let pos = robot.pos_x as usize;
-                line.replace_range(pos..pos + 1, "@");
+impl Render for Warehouse {
+    fn render(&self) -> Frame {
+        let mut frame = Frame::from_grid(&self.map, |item| {
+            let cell = Cell::new(item.to_char());
+            match item {
+                WarehouseItem::Nothing => cell.fg(Color::Gray),
+                WarehouseItem::Box => cell.fg(Color::Yellow),
+                WarehouseItem::Wall => cell.fg(Color::Blue),
+                WarehouseItem::Robot => cell.fg(Color::Red),
             }
-        }
-
-        println!("{}", line);
+        });
+        frame.set(self.robot, Cell::new(WarehouseItem::Robot.to_char()).fg(Color::Red));
+        return frame;
     }
 }
 
 pub fn simulate_robot_move(
     warehouse_map: &mut WarehouseMap,
-    robot: &mut WarehousePos,
+    robot: &mut Pos,
     move_dir: Direction,
 ) {
-    let next_robot_step = robot.next_pos(move_dir);
-    let item_at_new_pos = warehouse_map[next_robot_step.pos_y][next_robot_step.pos_x];
+    let next_robot_step = *robot + move_dir.vec();
+    let item_at_new_pos = warehouse_map[next_robot_step];
 
     match item_at_new_pos {
         WarehouseItem::Nothing => *robot = next_robot_step,
         WarehouseItem::Wall => {}
         WarehouseItem::Robot => panic!(),
         WarehouseItem::Box => {
-            let mut next_item_pos = next_robot_step.next_pos(move_dir);
-            let mut next_item = warehouse_map[next_item_pos.pos_y][next_item_pos.pos_x];
+            let mut next_item_pos = next_robot_step + move_dir.vec();
+            let mut next_item = warehouse_map[next_item_pos];
 
             loop {
                 if next_item == WarehouseItem::Nothing {
-                    warehouse_map[next_robot_step.pos_y][next_robot_step.pos_x] = WarehouseItem::Nothing;
-                    warehouse_map[next_item_pos.pos_y][next_item_pos.pos_x] = WarehouseItem::Box;
+                    warehouse_map[next_robot_step] = WarehouseItem::Nothing;
+                    warehouse_map[next_item_pos] = WarehouseItem::Box;
                     *robot = next_robot_step;
                     return;
                 } else if next_item == WarehouseItem::Box {
-                    next_item_pos = next_item_pos.next_pos(move_dir);
-                    next_item = warehouse_map[next_item_pos.pos_y][next_item_pos.pos_x];
+                    next_item_pos += move_dir.vec();
+                    next_item = warehouse_map[next_item_pos];
                 } else if next_item == WarehouseItem::Wall {
                     break;
                 }
@@ -132,65 +99,212 @@ pub fn simulate_robot_move(
     }
 }
 
-pub fn parse_directions(input: &str) -> DirectionList {
-    input
-        .trim()
-        .split("\n")
-        .map(|line| line.chars())
-        .flatten()
-        .map(|chr| Direction::from_char(chr))
-        .collect::<Vec<_>>()
+/// The robot going through its moves, one at a time.
+#[derive(Clone)]
+pub struct Moves<'a> {
+    pub warehouse: Warehouse,
+    move_list: &'a [Direction],
+    /// How many of the moves were made.
+    done: usize,
+    /// Where the boxes pushed by the last move ended up.
+    pushed: Vec<Pos>,
 }
 
-pub fn parse_map(input: &str) -> WarehouseMap {
-    let lines_iter = input.trim().split("\n");
+impl<'a> Moves<'a> {
+    pub fn new(warehouse_map: &WarehouseMap, move_list: &'a [Direction], robot: Pos) -> Moves<'a> {
+        Moves {
+            warehouse: Warehouse {
+                map: warehouse_map.clone(),
+                robot,
+            },
+            move_list,
+            done: 0,
+            pushed: Vec::new(),
+        }
+    }
+}
+
+impl Simulation for Moves<'_> {
+    type State = usize;
+
+    fn state(&self) -> usize {
+        self.done
+    }
+
+    fn step(&mut self) -> bool {
+        let Some(move_dir) = self.move_list.get(self.done) else {
+            return false;
+        };
+
+        // the boxes right in front of the robot move along if it does
+        let robot = self.warehouse.robot;
+        let boxes = (1..)
+            .map(|dist| robot + move_dir.vec() * dist)
+            .take_while(|pos| self.warehouse.map.get_pos(*pos) == Some(&WarehouseItem::Box))
+            .count() as i64;
+
+        simulate_robot_move(&mut self.warehouse.map, &mut self.warehouse.robot, *move_dir);
+        self.done += 1;
+        self.pushed.clear();
+        if self.warehouse.robot != robot {
+            let robot = self.warehouse.robot;
+            self.pushed.extend((1..=boxes).map(|dist| robot + move_dir.vec() * dist));
+        }
+        true
+    }
+}
+
+impl Render for Moves<'_> {
+    fn render(&self) -> Frame {
+        let mut frame = self.warehouse.render();
+        for pos in &self.pushed {
+            frame.set(*pos, Cell::new(WarehouseItem::Box.to_char()).fg(Color::Green));
+        }
+
+        match self.done {
+            0 => frame.with_caption(format!("{} moves to go", self.move_list.len())),
+            done => frame.with_caption(format!(
+                "move {done}/{}: {}, GPS sum {}",
+                self.move_list.len(),
+                self.move_list[done - 1].to_arrow(),
+                calc_gps_coords(&self.warehouse.map)
+            )),
+        }
+    }
+}
+
+impl Inspect for Moves<'_> {
+    fn details(&self) -> Vec<(&'static str, String)> {
+        let arrow = |move_dir: Option<&Direction>| {
+            move_dir.map_or("none".to_string(), |move_dir| move_dir.to_arrow().to_string())
+        };
+        let last_move = self.done.checked_sub(1).and_then(|idx| self.move_list.get(idx));
+        let pushed = match self.pushed.is_empty() {
+            true => "none".to_string(),
+            false => self.pushed.iter().map(Pos::to_string).collect::<Vec<_>>().join(" "),
+        };
+
+        vec![
+            ("move", format!("{}/{}", self.done, self.move_list.len())),
+            ("last move", arrow(last_move)),
+            ("next move", arrow(self.move_list.get(self.done))),
+            ("robot", self.warehouse.robot.to_string()),
+            ("boxes pushed", pushed),
+            ("GPS sum", calc_gps_coords(&self.warehouse.map).to_string()),
+        ]
+    }
+
+    fn focus(&self) -> Option<Pos> {
+        Some(self.warehouse.robot)
+    }
+}
 
-    let parse_line = |line: &str| {
-        line.chars()
-            .map(WarehouseItem::from_char)
-            .collect::<Vec<_>>()
-    };
+pub fn parse_directions(input: &str) -> Result<DirectionList, ParseError> {
+    input
+        .char_indices()
+        .filter(|(_, chr)| !chr.is_whitespace())
+        .map(|(idx, chr)| {
+            Direction::from_arrow(chr)
+                .ok_or_else(|| ParseError::at(input, &input[idx..], "one of `^`, `v`, `<`, `>`"))
+        })
+        .collect()
+}
 
-    lines_iter.map(parse_line).collect()
+pub fn parse_map(input: &str) -> Result<WarehouseMap, ParseError> {
+    Grid::try_parse(input, "one of `.`, `O`, `#`, `@`", WarehouseItem::from_char)
 }
 
-pub fn find_robot(input: &str) -> WarehousePos {
-    for (line_num, line) in input.split("\n").enumerate() {
-        if let Some(index) = line.chars().position(|chr| chr == '@') {
-            return WarehousePos {
-                pos_y: line_num,
-                pos_x: index,
-            };
+pub fn find_robot(input: &str) -> Result<Pos, ParseError> {
+    for (line_num, line) in input.trim().lines().enumerate() {
+        if let Some(index) = line.trim().chars().position(|chr| chr == '@') {
+            return Ok(Pos::from((line_num, index)));
         }
     }
 
-    panic!();
+    Err(ParseError::at_end(input, "a robot `@` somewhere on the map"))
 }
 
-pub fn parse_input(input: &str) -> (WarehouseMap, DirectionList, WarehousePos) {
-    let (warehouse_input, directions_input) = input.split_once("\n\n").unwrap();
+pub fn parse_input(input: &str) -> Result<(WarehouseMap, DirectionList, Pos), ParseError> {
+    // line and column numbers are unaffected by dropping the `\r` of CRLF line ends
+    let input = input.replace("\r\n", "\n");
+    let (warehouse_input, directions_input) = input
+        .split_once("\n\n")
+        .ok_or_else(|| ParseError::at_end(&input, "a blank line between the map and the moves"))?;
 
-    let direction_list = parse_directions(directions_input);
-    let warehouse_map = parse_map(warehouse_input);
-    let robot = find_robot(warehouse_input);
+    let direction_list = parse_directions(directions_input)
+        .map_err(|err| err.within(&input, directions_input))?;
+    let warehouse_map = parse_map(warehouse_input)?;
+    let robot = find_robot(warehouse_input)?;
 
-    return (warehouse_map, direction_list, robot);
+    return Ok((warehouse_map, direction_list, robot));
 }
 
 pub fn calc_gps_coords(warehouse_map: &WarehouseMap) -> u64 {
     let mut sum = 0_u64;
 
-    for (line_idx, line) in warehouse_map.iter().enumerate() {
-        for (item_idx, item) in line.iter().enumerate() {
-            if *item == WarehouseItem::Box {
-                sum += (100 * line_idx as u64) + (item_idx as u64);
-            }
-        }
+    for (line_idx, item_idx) in warehouse_map.positions_of(&WarehouseItem::Box) {
+        sum += (100 * line_idx as u64) + (item_idx as u64);
     }
 
     return sum;
 }
 
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = (WarehouseMap, DirectionList, Pos);
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1((warehouse_map, move_list, robot): &Self::Input) -> Result<u64, SolveError> {
+        let mut warehouse_map = warehouse_map.clone();
+        let mut robot = *robot;
+
+        for move_dir in move_list {
+            simulate_robot_move(&mut warehouse_map, &mut robot, *move_dir);
+        }
+
+        Ok(calc_gps_coords(&warehouse_map))
+    }
+
+    fn part2(_: &Self::Input) -> Result<u64, SolveError> {
+        Err(SolveError::NotImplemented)
+    }
+}
+
+impl Animate for Day15 {
+    fn frames((warehouse_map, move_list, robot): &Self::Input) -> impl Iterator<Item = Frame> + '_ {
+        let mut moves = Moves::new(warehouse_map, move_list, *robot);
+
+        std::iter::once(moves.render())
+            .chain(std::iter::from_fn(move || moves.step().then(|| moves.render())))
+    }
+}
+
+impl StepThrough for Day15 {
+    fn simulation((warehouse_map, move_list, robot): &Self::Input) -> impl Inspect + '_ {
+        Moves::new(warehouse_map, move_list, *robot)
+    }
+}
+
+impl Picture for Day15 {
+    /// The warehouse after the last move.
+    fn picture((warehouse_map, move_list, robot): &Self::Input) -> Frame {
+        let mut warehouse = Warehouse {
+            map: warehouse_map.clone(),
+            robot: *robot,
+        };
+        for move_dir in move_list {
+            simulate_robot_move(&mut warehouse.map, &mut warehouse.robot, *move_dir);
+        }
+        warehouse.render()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -202,7 +316,7 @@ mod tests {
 #...O..
 #......
 ";
-        let warehouse_map = parse_map(input);
+        let warehouse_map = parse_map(input).unwrap();
         let gps_score = calc_gps_coords(&warehouse_map);
         assert_eq!(gps_score, 104);
 
@@ -218,8 +332,45 @@ mod tests {
 #OO....OO#
 ##########
 ";
-        let warehouse_map = parse_map(input);
+        let warehouse_map = parse_map(input).unwrap();
         let gps_score = calc_gps_coords(&warehouse_map);
         assert_eq!(gps_score, 10092);
     }
+
+    #[test]
+    fn frames_test() {
+        let input = include_str!("../input/test_input.txt");
+        let input = parse_input(input).unwrap();
+        let frames: Vec<Frame> = Day15::frames(&input).collect();
+        assert_eq!(frames.len(), input.1.len() + 1);
+
+        let last = frames.last().unwrap().to_string();
+        assert!(last.ends_with(", GPS sum 10092"));
+        assert!(last.starts_with(
+            "##########
+#.O.O.OOO#
+#........#
+#OO......#
+#OO@.....#"
+        ));
+    }
+
+    #[test]
+    fn moves_test() {
+        let input = parse_input("#######\n#@OO..#\n#######\n\n>>>>").unwrap();
+        let mut moves = Moves::new(&input.0, &input.1, input.2);
+
+        assert!(moves.step());
+        assert_eq!(moves.pushed, [Pos::new(1, 3), Pos::new(1, 4)]);
+        assert!(moves.render().to_string().starts_with("#######\n#.@OO.#"));
+        assert!(moves.step());
+        assert_eq!(moves.details()[4], ("boxes pushed", "4,1 5,1".to_string()));
+        // against the wall
+        assert!(moves.step());
+        assert_eq!(moves.warehouse.robot, Pos::new(1, 3));
+        assert_eq!(moves.details()[4], ("boxes pushed", "none".to_string()));
+        assert!(moves.step());
+        assert!(!moves.step());
+        assert_eq!(moves.details()[0], ("move", "4/4".to_string()));
+    }
 }
@@ -1,38 +1,13 @@
-use std::path;
+use std::collections::HashSet;
 
-#[derive(PartialEq, Clone, Copy)]
-pub enum Direction {
-    East = 0,
-    South = 1,
-    West = 2,
-    North = 3,
-}
-
-impl Direction {
-    pub fn to_vec(&self) -> (isize, isize) {
-        match self {
-            Direction::East => (0, 1),
-            Direction::South => (1, 0),
-            Direction::West => (0, -1),
-            Direction::North => (-1, 0),
-        }
-    }
-}
-
-impl From<isize> for Direction {
-    fn from(value: isize) -> Self {
-        let value = value.rem_euclid(4);
-        match value {
-            0 => Direction::East,
-            1 => Direction::South,
-            2 => Direction::West,
-            3 => Direction::North,
-            _ => unreachable!(),
-        }
-    }
-}
+use aoc_anim::{Animate, Cell, Color, Frame, Picture};
+use aoc_common::{ParseError, Solution, SolveError};
+use aoc_grid::{Direction, Grid, Pos};
+use aoc_search::{astar, astar_all};
+use tracing::debug;
 
 #[derive(PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TraverseAction {
     Forward = 0,
     TurnLeft = -1,
@@ -60,60 +35,69 @@ impl TraverseAction {
     }
 }
 
-pub fn parse_input(input: &str) -> Vec<Vec<char>> {
-    input
-        .trim()
-        .lines()
-        .map(|line| line.trim().chars().collect())
-        .collect()
+pub fn parse_input(input: &str) -> Result<Labyrinth, ParseError> {
+    let labyrinth = Grid::try_parse(input, "one of `.`, `#`, `S`, `E`", |chr| {
+        matches!(chr, '.' | '#' | 'S' | 'E').then_some(chr)
+    })?;
+
+    if labyrinth.height() < 3 || labyrinth.width() < 3 {
+        return Err(ParseError::at_end(input, "a labyrinth of at least 3x3 cells"));
+    }
+
+    Ok(labyrinth)
 }
 
-pub fn get_start_pos(labyrinth: &Vec<Vec<char>>) -> (usize, usize) {
-    (labyrinth.len() - 2, 1)
+pub fn get_start_pos(labyrinth: &Labyrinth) -> Pos {
+    Pos::from((labyrinth.height() - 2, 1))
 }
 
-pub fn get_end_pos(labyrinth: &Vec<Vec<char>>) -> (usize, usize) {
-    (1, labyrinth[1].len() - 2)
+pub fn get_end_pos(labyrinth: &Labyrinth) -> Pos {
+    Pos::from((1, labyrinth.width() - 2))
 }
 
-pub type Labyrinth = Vec<Vec<char>>;
+pub type Labyrinth = Grid<char>;
 pub type ActionHistory = Vec<TraverseAction>;
-pub type Position = (usize, usize);
-pub type PositionHistory = Vec<Position>;
-
-pub fn draw_labyrinth_and_path(labyrinth: &Labyrinth, pos_history: &PositionHistory) {
-    let mut labyrinth = labyrinth.clone();
-
-    for pos in pos_history {
-        labyrinth[pos.0][pos.1] = 'O';
-    }
-
-    for row in labyrinth {
-        let str = String::from_iter(row.iter());
-        println!("{}", str);
-    }
+pub type PositionHistory = Vec<Pos>;
+
+fn labyrinth_frame(labyrinth: &Labyrinth) -> Frame {
+    Frame::from_grid(labyrinth, |tile| match tile {
+        '#' => Cell::new('#').fg(Color::Gray),
+        'S' | 'E' => Cell::new(*tile).fg(Color::Green),
+        _ => Cell::new(' '),
+    })
 }
 
-pub fn draw_labyrinth_and_path_2(labyrinth: &Labyrinth, path: &Path) {
-    let mut labyrinth = labyrinth.clone();
+/// The first `steps` positions of `path`, marking where the reindeer turned
+/// with `L` and `R`, and the reindeer itself at the last of them.
+pub fn path_frame(labyrinth: &Labyrinth, path: &Path, steps: usize) -> Frame {
+    let mut frame = labyrinth_frame(labyrinth);
+    let steps = steps.min(path.pos_history.len());
 
-    for (pos, action) in path.pos_history.iter().zip(path.action_history.iter()) {
+    for (pos, action) in path.pos_history[1.min(steps)..steps].iter().zip(path.action_history.iter()) {
         let chr = match action {
             TraverseAction::Forward => 'O',
             TraverseAction::TurnLeft => 'L',
             TraverseAction::TurnRight => 'R',
         };
-
-        labyrinth[pos.0][pos.1] = chr;
+        frame.set(*pos, Cell::new(chr).fg(Color::Cyan));
     }
 
-    for row in labyrinth {
-        let str = String::from_iter(row.iter());
-        println!("{}", str);
+    if let Some(pos) = steps.checked_sub(1).map(|idx| path.pos_history[idx]) {
+        let dir = match steps {
+            1 => start_state(labyrinth).1,
+            _ => Direction::ALL
+                .into_iter()
+                .find(|dir| path.pos_history[steps - 2] + dir.vec() == pos)
+                .unwrap(),
+        };
+        frame.set(pos, Cell::new(dir.to_arrow()).fg(Color::Red));
     }
+
+    return frame;
 }
 
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Path {
     pos_history: PositionHistory,
     action_history: ActionHistory,
@@ -121,7 +105,7 @@ pub struct Path {
 }
 
 impl Path {
-    pub fn new(init_pos: Position) -> Path {
+    pub fn new(init_pos: Pos) -> Path {
         Path {
             pos_history: vec![init_pos],
             action_history: vec![],
@@ -129,176 +113,136 @@ impl Path {
         }
     }
 
-    pub fn cost(&self) -> usize {
-        self.action_history
-            .iter()
-            .map(|item| match item {
-                TraverseAction::Forward => 1,
-                TraverseAction::TurnLeft => 1001,
-                TraverseAction::TurnRight => 1001,
-            })
-            .sum()
-    }
-
-    fn heuristic(&self, end_pos: Position) -> usize {
-        let current_pos = self.pos_history.last().unwrap();
-        end_pos.0.abs_diff(current_pos.0) + end_pos.1.abs_diff(current_pos.1)
-    }
-
-    fn total_estimated_cost(&self, end_pos: Position) -> usize {
-        self.cost() + self.heuristic(end_pos)
-    }
-
-    fn get_current_direction(&self) -> Direction {
-        let turns_count = self
-            .action_history
-            .iter()
-            .map(|action| match action {
-                TraverseAction::Forward => 0,
-                TraverseAction::TurnLeft => -1,
-                TraverseAction::TurnRight => 1,
-            })
-            .sum::<isize>()
-            % (4);
-
-        let current_dir = Direction::from(turns_count.rem_euclid(4));
-
-        return current_dir;
-    }
-
-    fn get_current_pos(&self) -> Position {
-        *self.pos_history.last().unwrap()
-    }
+    fn from_states(states: &[Reindeer], cost: usize) -> Path {
+        let mut path = Path::new(states[0].0);
 
-    fn try_advance(&self) -> Vec<Path> {
-        let current_dir = self.get_current_direction();
-        let current_pos = self.get_current_pos();
-
-        let mut next_paths = Vec::<Path>::new();
-
-        for next_dir in [
-            Direction::East,
-            Direction::North,
-            Direction::South,
-            Direction::West,
-        ] {
-            let diff = ((next_dir as isize) - (current_dir as isize)).rem_euclid(4);
-
-            // action would be going back
-            if diff.abs() == 2 {
-                continue;
-            }
-
-            let action = TraverseAction::from(diff);
-            let next_pos_vec = next_dir.to_vec();
-            let new_pos = (
-                ((current_pos.0 as isize) + next_pos_vec.0) as usize,
-                ((current_pos.1 as isize) + next_pos_vec.1) as usize,
-            );
-
-            let mut new_path = self.to_owned();
-            new_path.action_history.push(action);
-            new_path.cost += action.cost();
-            new_path.pos_history.push(new_pos);
-
-            next_paths.push(new_path);
+        for step in states.windows(2) {
+            let ((_, from_dir), (to_pos, to_dir)) = (step[0], step[1]);
+            path.action_history.push(TraverseAction::from(from_dir.turns_to(to_dir) as isize));
+            path.pos_history.push(to_pos);
         }
 
-        return next_paths;
+        path.cost = cost;
+        return path;
     }
 
-    // pub fn get_dist_to(&self, other: &Self) -> usize{
-    //     let self_pos = self.get_current_pos();
-    //     let other_pos = other.get_current_pos();
-    //     let advance_score = self_pos.0.abs_diff(other_pos.0) + self_pos.1.abs_diff(other_pos.1);
-
-    //     let self_dir = self.get_current_direction();
-    //     let other_dir = other.get_current_direction();
-    //     let turn_score = 1000 * (self_dir as isize).abs_diff(other_dir as isize);
-
-    //     return advance_score + turn_score;
-    // }
-
     pub fn get_pos_history(&self) -> PositionHistory {
         self.pos_history.clone()
     }
 }
 
-impl PartialEq for Path {
-    fn eq(&self, other: &Self) -> bool {
-        return (self.cost == other.cost)
-            && (self.get_current_pos() == other.get_current_pos())
-            && (self.get_current_direction() == other.get_current_direction());
-    }
+/// Where the reindeer stands and which way it is facing.
+type Reindeer = (Pos, Direction);
+
+fn next_moves(labyrinth: &Labyrinth, (pos, dir): &Reindeer) -> Vec<(Reindeer, usize)> {
+    [
+        TraverseAction::Forward,
+        TraverseAction::TurnLeft,
+        TraverseAction::TurnRight,
+    ]
+    .into_iter()
+    .map(|action| {
+        let next_dir = dir.rotate(action as isize);
+        ((*pos + next_dir.vec(), next_dir), action.cost())
+    })
+    .filter(|((next_pos, _), _)| labyrinth.get_pos(*next_pos).is_some_and(|tile| *tile != '#'))
+    .collect()
+}
+
+fn start_state(labyrinth: &Labyrinth) -> Reindeer {
+    // the reindeer starts out facing east
+    (get_start_pos(labyrinth), Direction::Right)
 }
 
 pub fn find_path(labyrinth: &Labyrinth) -> Option<Path> {
-    let start_pos = get_start_pos(labyrinth);
     let end_pos = get_end_pos(labyrinth);
 
-    let mut open_list = Vec::<Path>::new();
-    let mut closed_list = Vec::<Position>::new();
+    let path = astar(
+        start_state(labyrinth),
+        |reindeer| next_moves(labyrinth, reindeer),
+        |(pos, _)| pos.manhattan(end_pos) as usize,
+        |(pos, _)| *pos == end_pos,
+    )?;
 
-    open_list.push(Path::new(start_pos));
+    debug!(cost = path.cost, steps = path.states.len() - 1, "found a cheapest path");
+    Some(Path::from_states(&path.states, path.cost))
+}
 
-    let mut paths = Vec::<Path>::new();
+/// Tiles that are part of at least one of the cheapest paths.
+pub fn best_path_tiles(labyrinth: &Labyrinth) -> Option<HashSet<Pos>> {
+    let end_pos = get_end_pos(labyrinth);
 
-    while !open_list.is_empty() {
-        open_list.sort_by(|left, right| {
-            left.total_estimated_cost(end_pos)
-                .cmp(&right.total_estimated_cost(end_pos))
-        });
-        open_list.reverse();
+    let best_paths = astar_all(
+        start_state(labyrinth),
+        |reindeer| next_moves(labyrinth, reindeer),
+        |(pos, _)| pos.manhattan(end_pos) as usize,
+        |(pos, _)| *pos == end_pos,
+    )?;
 
-        let current_path = open_list.pop().unwrap();
-        let current_pos = current_path.get_current_pos();
+    Some(best_paths.states().into_iter().map(|(pos, _)| pos).collect())
+}
+
+/// Number of tiles that are part of at least one of the cheapest paths.
+pub fn count_best_path_tiles(labyrinth: &Labyrinth) -> Option<usize> {
+    best_path_tiles(labyrinth).map(|tiles| tiles.len())
+}
 
-   //     draw_labyrinth_and_path_2(labyrinth, &current_path);
+/// Every tile that is part of at least one of the cheapest paths.
+pub fn best_tiles_frame(labyrinth: &Labyrinth) -> Option<Frame> {
+    let tiles = best_path_tiles(labyrinth)?;
+    let mut frame = labyrinth_frame(labyrinth);
+    for pos in &tiles {
+        frame.set(*pos, Cell::new('O').fg(Color::Cyan));
+    }
+    Some(frame.with_caption(format!("{} tiles are on a best path", tiles.len())))
+}
 
-        if current_pos == end_pos {
-            assert!(current_path.action_history.len() == current_path.pos_history.len() - 1);
-            assert!(current_path.cost == current_path.cost());
+pub struct Day16;
 
-            //return Some(current_path);
-            paths.push(current_path);
-            continue;
-        }
+impl Solution for Day16 {
+    type Input = Labyrinth;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-        closed_list.push(current_pos);
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
 
-        for neighbour in current_path.try_advance() {
-            let next_pos = neighbour.get_current_pos();
+    fn part1(labyrinth: &Self::Input) -> Result<usize, SolveError> {
+        let path = find_path(labyrinth).ok_or(SolveError::NoSolution)?;
+        Ok(path.cost)
+    }
 
-            if labyrinth[next_pos.0][next_pos.1] == '#' {
-                continue;
-            }
-            if closed_list.contains(&next_pos) {
-                continue;
-            }
+    fn part2(labyrinth: &Self::Input) -> Result<usize, SolveError> {
+        count_best_path_tiles(labyrinth).ok_or(SolveError::NoSolution)
+    }
+}
 
-            let visited_neighbour_idx = open_list
-                .iter()
-                .position(|path| path.get_current_pos() == current_pos);
+impl Animate for Day16 {
+    /// The reindeer walking one of the cheapest paths, then every tile on
+    /// any of them.
+    fn frames(labyrinth: &Self::Input) -> impl Iterator<Item = Frame> + '_ {
+        let walk = find_path(labyrinth).into_iter().flat_map(move |path| {
+            (1..=path.pos_history.len()).map(move |steps| {
+                let cost: usize = path.action_history[..steps - 1]
+                    .iter()
+                    .map(TraverseAction::cost)
+                    .sum();
+                path_frame(labyrinth, &path, steps)
+                    .with_caption(format!("step {}, score {cost} of {}", steps - 1, path.cost))
+            })
+        });
 
-            if visited_neighbour_idx.is_none() {
-                open_list.push(neighbour);
-                continue;
-            }
+        let tiles = std::iter::once_with(move || best_tiles_frame(labyrinth)).flatten();
 
-            let visited_neighbour = open_list.get_mut(visited_neighbour_idx.unwrap()).unwrap();
-            if visited_neighbour.cost > neighbour.cost {
-                // newly discovered path to the same position is better
-                *visited_neighbour = neighbour;
-            }
-        }
+        walk.chain(tiles)
     }
+}
 
-    paths.sort_by(|left, right| left.cost.cmp(&right.cost));
-    for path in paths.iter() {
-        draw_labyrinth_and_path_2(labyrinth, path);
-        println!()
+impl Picture for Day16 {
+    fn picture(labyrinth: &Self::Input) -> Frame {
+        best_tiles_frame(labyrinth).unwrap_or_else(|| labyrinth_frame(labyrinth))
     }
-    return paths.first().cloned();
 }
 
 #[cfg(test)]
@@ -312,7 +256,7 @@ mod tests {
             #.#
             ###        
             ";
-        let labyrinth = parse_input(input);
+        let labyrinth = parse_input(input).unwrap();
         let path = find_path(&labyrinth).unwrap();
         assert!(path.cost == 0);
 
@@ -321,7 +265,7 @@ mod tests {
             #..#
             ####        
             ";
-        let labyrinth = parse_input(input);
+        let labyrinth = parse_input(input).unwrap();
         let path = find_path(&labyrinth).unwrap();
         assert!(path.cost == 1);
 
@@ -331,7 +275,7 @@ mod tests {
             #.#
             ###        
             ";
-        let labyrinth = parse_input(input);
+        let labyrinth = parse_input(input).unwrap();
         let path = find_path(&labyrinth).unwrap();
         assert!(path.cost == 1001);
 
@@ -341,7 +285,7 @@ mod tests {
             #..#
             ####        
             ";
-        let labyrinth = parse_input(input);
+        let labyrinth = parse_input(input).unwrap();
         let path = find_path(&labyrinth).unwrap();
         assert!(path.cost == 1002);
 
@@ -352,7 +296,7 @@ mod tests {
             #.#
             ###        
         ";
-        let labyrinth = parse_input(input);
+        let labyrinth = parse_input(input).unwrap();
         let path = find_path(&labyrinth);
         assert!(path.is_none());
 
@@ -363,7 +307,7 @@ mod tests {
             #...#
             #####        
         ";
-        let labyrinth = parse_input(input);
+        let labyrinth = parse_input(input).unwrap();
         let path = find_path(&labyrinth).unwrap();
         assert!(path.cost == 1004);
 
@@ -377,9 +321,9 @@ mod tests {
             #.#...#
             #######        
         ";
-        let labyrinth = parse_input(input);
+        let labyrinth = parse_input(input).unwrap();
         let path = find_path(&labyrinth).unwrap();
-        draw_labyrinth_and_path_2(&labyrinth, &path);
+        println!("{}", path_frame(&labyrinth, &path, usize::MAX));
         assert_eq!(path.cost, 5015);
     }
 
@@ -402,10 +346,31 @@ mod tests {
 #S...................#.............................#
 ####################################################   
         ";
-        let labyrinth = parse_input(input);
+        let labyrinth = parse_input(input).unwrap();
         let path = find_path(&labyrinth).unwrap();
         
-        draw_labyrinth_and_path_2(&labyrinth, &path);
+        println!("{}", path_frame(&labyrinth, &path, usize::MAX));
         assert_eq!(path.cost, 5078);
     }
+
+    #[test]
+    fn path_frame_test() {
+        let input = std::fs::read_to_string("input/test_input.txt").unwrap();
+        let labyrinth = parse_input(&input).unwrap();
+        let path = find_path(&labyrinth).unwrap();
+        // no step taken yet, not even onto the start
+        assert_eq!(path_frame(&labyrinth, &path, 0), labyrinth_frame(&labyrinth));
+        assert_ne!(path_frame(&labyrinth, &path, 1), labyrinth_frame(&labyrinth));
+    }
+
+    #[test]
+    fn best_path_tiles() {
+        let input = std::fs::read_to_string("input/test_input.txt").unwrap();
+        let labyrinth = parse_input(&input).unwrap();
+        assert_eq!(count_best_path_tiles(&labyrinth), Some(45));
+
+        let input = std::fs::read_to_string("input/test_input_2.txt").unwrap();
+        let labyrinth = parse_input(&input).unwrap();
+        assert_eq!(count_best_path_tiles(&labyrinth), Some(64));
+    }
 }
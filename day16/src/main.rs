@@ -1,20 +1,7 @@
-use std::fs;
+use std::process::ExitCode;
 
-use day16::*;
+use day16::Day16;
 
-fn main() {
-    let input_path = "input/input.txt";
-    // let input_path = "input/test_input.txt";
-    // let input_path = "input/test_input_2.txt";
-
-    let input = fs::read_to_string(input_path).unwrap();
-
-    let labyrinth = parse_input(&input);
-
-    let path = find_path(&labyrinth).unwrap();
-
-    //draw_labyrinth_and_path(&labyrinth, &path.get_pos_history());
-    draw_labyrinth_and_path_2(&labyrinth, &path);
-    
-    println!("Result: {}", path.cost());
+fn main() -> ExitCode {
+    aoc_common::run_main_rendered::<Day16>(16, aoc_anim::save_picture::<Day16>)
 }
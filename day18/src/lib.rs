@@ -1,155 +1,324 @@
-use std::{rc::Rc, usize};
+use aoc_anim::{Cell, Color, Frame, Inspect, Picture, Render, StepThrough};
+use aoc_common::{parse_number, Param, Params, ParseError, Simulation, Solution, SolveError};
+use aoc_grid::{Direction, Grid, Pos};
+use aoc_search::{bfs, Path};
+use tracing::debug;
 
-pub type Pos = (u64, u64);
-
-#[derive(Clone)]
-pub struct Node {
-    pos: Pos,
-    parent: Option<Rc<Node>>,
-}
-
-pub fn parse_input(input: &str, len: usize) -> Vec<Pos> {
-    let parse_line = |line: &str| {
-        let (left, right) = line.split_once(',').unwrap();
-        (right.parse::<u64>().unwrap(), left.parse::<u64>().unwrap())
+pub fn parse_input(input: &str, len: usize) -> Result<Vec<Pos>, ParseError> {
+    let parse_line = |line: &str| -> Result<Pos, ParseError> {
+        let line = line.trim();
+        let (left, right) = line
+            .split_once(',')
+            .ok_or_else(|| ParseError::at(input, line, "`<x>,<y>`"))?;
+        Ok(Pos::new(parse_number(input, right)?, parse_number(input, left)?))
     };
 
     input
-        .trim()
         .lines()
-        .enumerate()
-        .filter(|(idx, _)| *idx < len)
-        .map(|(_, line)| parse_line(line))
+        .filter(|line| !line.trim().is_empty())
+        .take(len)
+        .map(parse_line)
         .collect()
 }
 
-pub fn path_cost(node: &Node) -> u64 {
-    if node.parent.is_none() {
-        return 0;
+/// Builds a `grid_size` x `grid_size` map where corrupted cells are `true`.
+/// Bytes falling outside of it cannot block anything and are left out.
+pub fn build_memory_map(grid_size: usize, corrupted_blocks: &[Pos]) -> Grid<bool> {
+    let mut memory_map = Grid::new(grid_size, grid_size, false);
+    for block in corrupted_blocks {
+        if let Some(cell) = block.to_idx().and_then(|idx| memory_map.get_mut(idx)) {
+            *cell = true;
+        }
     }
-
-    return 1 + path_cost(node.parent.as_ref().unwrap());
-}
-
-pub fn heuristic(node: &Node, target: Pos) -> u64 {
-    let node_pos = node.pos;
-    node_pos.0.abs_diff(target.0) + node_pos.0.abs_diff(target.0)
-}
-
-pub fn total_estimated_cost(node: &Node, target: Pos) -> u64 {
-    path_cost(node) + heuristic(node, target)
+    return memory_map;
 }
 
+/// Shortest path from `start_point` to `end_point` through uncorrupted memory.
 pub fn find_path(
-    grid_size: u64,
-    corrupted_blocks: &[Pos],
+    memory_map: &Grid<bool>,
     start_point: Pos,
     end_point: Pos,
-) -> Option<Node> {
-    let root = Node {
-        pos: start_point,
-        parent: None,
+) -> Option<Path<Pos, usize>> {
+    let neighbours = |pos: &Pos| {
+        Direction::ALL
+            .map(|dir| *pos + dir.vec())
+            .into_iter()
+            // outside of the memory space or corrupted
+            .filter(|next_pos| memory_map.get_pos(*next_pos) == Some(&false))
     };
 
-    let mut open_list: Vec<Node> = vec![root];
-    let mut closed_list: Vec<Pos> = vec![];
+    bfs(start_point, neighbours, |pos| *pos == end_point)
+}
 
-    while !open_list.is_empty() {
-        open_list.sort_by_key(|node| total_estimated_cost(node, end_point) as i64 * -1);
+pub fn draw_grid(memory_map: &Grid<bool>, path: &Vec<Pos>){
+    let mut picture = memory_map.map(|corrupted| if *corrupted { '#' } else { '.' });
+    for pos in path {
+        picture[*pos] = 'O';
+    }
+    debug!("memory space:\n{picture}");
+}
 
-        let current_node = open_list.pop().unwrap();
-        let current_cost = path_cost(&current_node);
+/// Highest `max-coord`, for a memory space of some 16 million cells.
+const MAX_COORD: u64 = 4095;
 
-        if current_node.pos == end_point {
-            return Some(current_node);
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "SerdeMemorySpace")
+)]
+pub struct MemorySpace {
+    /// Every falling byte, in order.
+    pub corrupted_blocks: Vec<Pos>,
+    pub grid_size: usize,
+    /// How many bytes have fallen for part 1.
+    pub input_len: usize,
+}
+
+/// A [`MemorySpace`] as it is read, before its size is checked.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct SerdeMemorySpace {
+    corrupted_blocks: Vec<Pos>,
+    grid_size: usize,
+    input_len: usize,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<SerdeMemorySpace> for MemorySpace {
+    type Error = String;
+
+    fn try_from(memory: SerdeMemorySpace) -> Result<MemorySpace, String> {
+        if memory.grid_size == 0 || memory.grid_size as u64 > MAX_COORD + 1 {
+            return Err(format!(
+                "a {0}x{0} memory space is not between 1x1 and {1}x{1}",
+                memory.grid_size,
+                MAX_COORD + 1
+            ));
         }
+        Ok(MemorySpace {
+            corrupted_blocks: memory.corrupted_blocks,
+            grid_size: memory.grid_size,
+            input_len: memory.input_len,
+        })
+    }
+}
 
-        let new_neighbours_pos = get_neighbours(&current_node, grid_size);
-        for neighbour_pos in new_neighbours_pos.iter() {
-            if corrupted_blocks.contains(&neighbour_pos) {
-                continue;
-            }
-            if closed_list.contains(&neighbour_pos) {
-                continue;
-            }
+impl MemorySpace {
+    fn start_point(&self) -> Pos {
+        Pos::new(0, 0)
+    }
 
-            let neighbour_node = Node {
-                pos: *neighbour_pos,
-                parent: Some(Rc::new(current_node.clone())),
-            };
-
-            let index = open_list
-                .iter()
-                .position(|node| node.pos == neighbour_node.pos);
-            if let Some(index) = index {
-                // checking if we found a better way to reach this node
-                let other_node = open_list.get_mut(index).unwrap();
-                let other_cost = path_cost(other_node);
-                if (current_cost + 1) < other_cost {
-                    other_node.parent = Some(Rc::new(current_node.clone()));
-                }
-            } else {
-                open_list.push(neighbour_node);
-            }
+    fn end_point(&self) -> Pos {
+        Pos::from((self.grid_size - 1, self.grid_size - 1))
+    }
+
+    /// The memory space once the bytes of part 1 have fallen.
+    fn fallen_map(&self) -> Grid<bool> {
+        // inputs shorter than `input_len` simply have fewer bytes fallen
+        let fallen = self.input_len.min(self.corrupted_blocks.len());
+        build_memory_map(self.grid_size, &self.corrupted_blocks[0..fallen])
+    }
+}
+
+/// The bytes falling one at a time, with the shortest path through what is
+/// left after each of them.
+#[derive(Clone)]
+pub struct ByteFall<'a> {
+    memory: &'a MemorySpace,
+    /// How many bytes have fallen.
+    fallen: usize,
+    memory_map: Grid<bool>,
+    path: Option<Path<Pos, usize>>,
+}
+
+impl<'a> ByteFall<'a> {
+    pub fn new(memory: &'a MemorySpace) -> ByteFall<'a> {
+        let memory_map = build_memory_map(memory.grid_size, &[]);
+        let path = find_path(&memory_map, memory.start_point(), memory.end_point());
+        ByteFall {
+            memory,
+            fallen: 0,
+            memory_map,
+            path,
         }
+    }
 
-        closed_list.push(current_node.pos);
+    fn last_byte(&self) -> Option<Pos> {
+        self.fallen.checked_sub(1).map(|idx| self.memory.corrupted_blocks[idx])
     }
-    return None;
 }
 
-fn get_neighbours(node: &Node, grid_size: u64) -> Vec<Pos> {
-    let mut neighbours: Vec<Pos> = vec![];
-    let node_pos = node.pos;
+impl Simulation for ByteFall<'_> {
+    type State = usize;
 
-    if node.pos.0 > 0 {
-        // top
-        neighbours.push((node_pos.0 - 1, node_pos.1));
+    fn state(&self) -> usize {
+        self.fallen
     }
-    if node.pos.0 < grid_size - 1 {
-        // bottom
-        neighbours.push((node_pos.0 + 1, node_pos.1));
+
+    fn step(&mut self) -> bool {
+        let Some(byte) = self.memory.corrupted_blocks.get(self.fallen) else {
+            return false;
+        };
+
+        self.fallen += 1;
+        if let Some(cell) = byte.to_idx().and_then(|idx| self.memory_map.get_mut(idx)) {
+            *cell = true;
+        }
+        // a path the byte missed is still one of the shortest
+        if self.path.as_ref().is_some_and(|path| path.states.contains(byte)) {
+            let memory = self.memory;
+            self.path = find_path(&self.memory_map, memory.start_point(), memory.end_point());
+        }
+        true
     }
-    if node.pos.1 > 0 {
-        // left
-        neighbours.push((node_pos.0, node_pos.1 - 1));
+}
+
+impl Render for ByteFall<'_> {
+    fn render(&self) -> Frame {
+        let mut frame = Frame::from_grid(&self.memory_map, |corrupted| match corrupted {
+            true => Cell::new('#').fg(Color::Gray),
+            false => Cell::new('.'),
+        });
+        for pos in self.path.iter().flat_map(|path| &path.states) {
+            frame.set(*pos, Cell::new('O').fg(Color::Green));
+        }
+        if let Some(byte) = self.last_byte() {
+            frame.set(byte, Cell::new('#').fg(Color::Red));
+        }
+
+        let total = self.memory.corrupted_blocks.len();
+        match &self.path {
+            Some(path) => frame.with_caption(format!(
+                "{}/{total} bytes fallen, the way out takes {} steps",
+                self.fallen, path.cost
+            )),
+            None => frame.with_caption(format!(
+                "{}/{total} bytes fallen, there is no way out",
+                self.fallen
+            )),
+        }
     }
-    if node.pos.1 < grid_size - 1 {
-        // right
-        neighbours.push((node_pos.0, node_pos.1 + 1));
+}
+
+impl Inspect for ByteFall<'_> {
+    fn details(&self) -> Vec<(&'static str, String)> {
+        let total = self.memory.corrupted_blocks.len();
+        let last_byte = self.last_byte().map_or("none".to_string(), |byte| byte.to_string());
+        let path = match &self.path {
+            Some(path) => format!("{} steps", path.cost),
+            None => "blocked".to_string(),
+        };
+        vec![
+            ("bytes fallen", format!("{}/{total}", self.fallen)),
+            ("last byte", last_byte),
+            ("way out", path),
+        ]
     }
 
-    return neighbours;
+    fn focus(&self) -> Option<Pos> {
+        self.last_byte()
+    }
 }
 
-pub fn unroll_path(node: &Node) -> Vec<Pos> {
-    if node.parent.is_none() {
-        vec![node.pos]
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input = MemorySpace;
+    type Answer1 = usize;
+    type Answer2 = String;
+
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "max-coord",
+            help: "Highest x and y coordinate in the memory space",
+            default: 70,
+            max: MAX_COORD,
+        },
+        Param {
+            name: "bytes",
+            help: "How many bytes have fallen for part 1",
+            default: 1024,
+            max: u64::MAX,
+        },
+    ];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let mut memory = MemorySpace {
+            corrupted_blocks: parse_input(input, usize::MAX)?,
+            grid_size: 0,
+            input_len: 0,
+        };
+        Self::configure(&mut memory, &Params::new(Self::PARAMS));
+        Ok(memory)
     }
-    else {
-        let mut vec = unroll_path(node.parent.as_ref().unwrap().as_ref());
-        vec.push(node.pos);
-        return vec;
+
+    fn configure(memory: &mut Self::Input, params: &Params) {
+        // at most `MAX_COORD`, so one more still fits
+        memory.grid_size = params.get("max-coord") as usize + 1;
+        memory.input_len = usize::try_from(params.get("bytes")).unwrap_or(usize::MAX);
     }
 
-}
+    fn part1(memory: &Self::Input) -> Result<usize, SolveError> {
+        let memory_map = memory.fallen_map();
+        let path = find_path(
+            &memory_map,
+            memory.start_point(),
+            memory.end_point(),
+        )
+        .ok_or(SolveError::NoSolution)?;
 
-pub fn draw_grid(grid_size: u64, corrupted_blocks: &[Pos], path: &Vec<Pos>){
-    for line_idx in 0..grid_size {
-        for column_idx in 0..grid_size {
-            if corrupted_blocks.contains(&(line_idx, column_idx)){
-                print!("{}", '#');
-            }
-            else if path.contains(&(line_idx, column_idx)){
-                print!("{}", 'O');
-            }
-            else {
-                print!("{}", '.');
+        Ok(path.cost)
+    }
+
+    fn part2(memory: &Self::Input) -> Result<String, SolveError> {
+        let corrupted_blocks = &memory.corrupted_blocks;
+        let is_open = |fallen: usize| {
+            let memory_map = build_memory_map(memory.grid_size, &corrupted_blocks[0..fallen]);
+            find_path(&memory_map, memory.start_point(), memory.end_point()).is_some()
+        };
+
+        // numbers of fallen bytes leaving the exit reachable, and cutting it off
+        let mut known_good = 0;
+        let mut known_bad = corrupted_blocks.len();
+        if !is_open(known_good) || is_open(known_bad) {
+            return Err(SolveError::NoSolution);
+        }
+
+        while known_bad > known_good + 1 {
+            let test_point = known_good + (known_bad - known_good) / 2;
+            if is_open(test_point) {
+                known_good = test_point;
+            } else {
+                known_bad = test_point;
             }
         }
-        print!("\n");
+
+        Ok(corrupted_blocks[known_bad - 1].to_string())
     }
+}
+
+impl StepThrough for Day18 {
+    fn simulation(memory: &Self::Input) -> impl Inspect + '_ {
+        ByteFall::new(memory)
+    }
+}
+
+impl Picture for Day18 {
+    /// The memory space of part 1 with the shortest path through it.
+    fn picture(memory: &Self::Input) -> Frame {
+        let memory_map = memory.fallen_map();
+        let mut frame = Frame::from_grid(&memory_map, |corrupted| match corrupted {
+            true => Cell::new('#').fg(Color::Gray),
+            false => Cell::new('.'),
+        });
 
+        let path = find_path(&memory_map, memory.start_point(), memory.end_point());
+        for pos in path.iter().flat_map(|path| &path.states) {
+            frame.set(*pos, Cell::new('O').fg(Color::Green));
+        }
+
+        frame
+    }
 }
 
 #[cfg(test)]
@@ -158,21 +327,85 @@ mod tests {
 
     #[test]
     fn it_works() {
-        let root = Rc::new(Node {
-            pos: (0, 0),
-            parent: None,
-        });
-        let node_1 = Rc::new(Node {
-            pos: (0, 0),
-            parent: Some(Rc::clone(&root)),
-        });
-        let node_2 = Rc::new(Node {
-            pos: (0, 0),
-            parent: Some(Rc::clone(&node_1)),
-        });
+        let memory_map = build_memory_map(3, &[Pos::new(1, 0), Pos::new(1, 1)]);
+
+        let path = find_path(&memory_map, Pos::new(0, 0), Pos::new(0, 0)).unwrap();
+        assert_eq!(path.cost, 0);
+
+        let path = find_path(&memory_map, Pos::new(0, 0), Pos::new(2, 0)).unwrap();
+        assert_eq!(path.cost, 6);
+        assert_eq!(path.states.len(), 7);
+
+        let memory_map = build_memory_map(3, &[Pos::new(1, 0), Pos::new(1, 1), Pos::new(1, 2)]);
+        assert!(find_path(&memory_map, Pos::new(0, 0), Pos::new(2, 0)).is_none());
+    }
+
+    #[test]
+    fn max_coord_test() {
+        let mut params = Params::new(Day18::PARAMS);
+        assert!(params.set("max-coord", u64::MAX).is_err());
+        assert!(params.set("max-coord", MAX_COORD + 1).is_err());
+        params.set("max-coord", MAX_COORD).unwrap();
+        params.set("bytes", u64::MAX).unwrap();
+
+        let mut memory = Day18::parse("1,1\n").unwrap();
+        Day18::configure(&mut memory, &params);
+        assert_eq!(memory.grid_size, 4096);
+        assert_eq!(memory.input_len, usize::MAX);
+    }
+
+    #[test]
+    fn example_path() {
+        let input = std::fs::read_to_string("input/test_input.txt").unwrap();
+        let corrupted_blocks = parse_input(&input, 12).unwrap();
+        let memory_map = build_memory_map(7, &corrupted_blocks);
+
+        let path = find_path(&memory_map, Pos::new(0, 0), Pos::new(6, 6)).unwrap();
+        assert_eq!(path.cost, 22);
+    }
+
+    #[test]
+    fn blocking_byte_test() {
+        let input = std::fs::read_to_string("input/test_input.txt").unwrap();
+        let mut memory = Day18::parse(&input).unwrap();
+        memory.grid_size = 7;
+        assert_eq!(Day18::part2(&memory), Ok("6,1".to_string()));
+
+        // no bytes, or none of them in the way
+        for input in ["", "5,5\n1,1", "0,0"] {
+            let mut memory = Day18::parse(input).unwrap();
+            memory.grid_size = 3;
+            assert_eq!(Day18::part2(&memory), Err(SolveError::NoSolution), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn picture_test() {
+        let input = std::fs::read_to_string("input/test_input.txt").unwrap();
+        let mut memory = Day18::parse(&input).unwrap();
+        memory.grid_size = 7;
+        memory.input_len = 12;
+
+        let picture = Day18::picture(&memory).to_string();
+        assert_eq!(picture.matches('#').count(), 12);
+        assert_eq!(picture.matches('O').count(), 23);
+    }
+
+    #[test]
+    fn byte_fall_test() {
+        let input = std::fs::read_to_string("input/test_input.txt").unwrap();
+        let mut memory = Day18::parse(&input).unwrap();
+        memory.grid_size = 7;
+
+        let mut byte_fall = ByteFall::new(&memory);
+        assert_eq!(byte_fall.path.as_ref().map(|path| path.cost), Some(12));
+        assert_eq!(byte_fall.run_for(12), 12);
+        assert_eq!(byte_fall.path.as_ref().map(|path| path.cost), Some(22));
+        assert_eq!(byte_fall.details()[1], ("last byte", "5,1".to_string()));
 
-        assert_eq!(path_cost(root.as_ref()), 0);
-        assert_eq!(path_cost(node_1.as_ref()), 1);
-        assert_eq!(path_cost(node_2.as_ref()), 2);
+        // the first byte to cut off the exit is the answer to part 2
+        byte_fall.run_until(|byte_fall| byte_fall.path.is_none());
+        assert_eq!(byte_fall.last_byte(), Some(Pos::new(1, 6)));
+        assert!(byte_fall.render().caption().ends_with("there is no way out"));
     }
 }
@@ -1,21 +1,45 @@
 use std::collections::{HashMap, HashSet};
 
+use tracing::trace;
+
+use aoc_common::{checked_sum, par_map_with, Memo, ParseError, Solution, SolveError};
+
 pub type TowelMap = HashMap<usize, String>;
 pub type TowelSet = HashSet<String>;
-pub type DesignCache = HashMap<String, Option<Vec<usize>>>;
-pub type DesignCache2 = HashMap<String, usize>;
+pub type DesignCache = Memo<String, Option<Vec<usize>>>;
+pub type DesignCache2 = Memo<String, Result<usize, SolveError>>;
+
+const STRIPE_COLORS: &str = "wubrg";
+
+/// Checks that `pattern`, a subslice of `source`, only contains stripe colors.
+fn check_stripes(source: &str, pattern: &str) -> Result<String, ParseError> {
+    match pattern.char_indices().find(|(_, chr)| !STRIPE_COLORS.contains(*chr)) {
+        Some((idx, _)) => Err(ParseError::at(
+            source,
+            &pattern[idx..],
+            "a stripe color (one of `w`, `u`, `b`, `r`, `g`)",
+        )),
+        None if pattern.is_empty() => Err(ParseError::at(source, pattern, "a stripe pattern")),
+        None => Ok(pattern.to_string()),
+    }
+}
 
-pub fn parse_designs(input_designs: &str) -> Vec<String> {
-    input_designs.lines().map(str::to_string).collect()
+pub fn parse_designs(input_designs: &str) -> Result<Vec<String>, ParseError> {
+    input_designs
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| check_stripes(input_designs, line))
+        .collect()
 }
 
-pub fn parse_towels(input_towels: &str) -> HashMap<usize, String> {
-    let iter = input_towels
+pub fn parse_towels(input_towels: &str) -> Result<HashMap<usize, String>, ParseError> {
+    let towels = input_towels
         .trim()
-        .split(", ")
-        .map(str::to_string)
-        .enumerate();
-    HashMap::from_iter(iter)
+        .split(',')
+        .map(|towel| check_stripes(input_towels, towel.trim()))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(HashMap::from_iter(towels.into_iter().enumerate()))
 }
 
 pub fn decompose_design(
@@ -23,78 +47,109 @@ pub fn decompose_design(
     towels: &TowelMap,
     cache: &mut DesignCache,
 ) -> Option<Vec<usize>> {
-    //println!("Testing string {}", design);
-
-    if cache.contains_key(design) {
-        return cache.get(design).unwrap().to_owned();
-    }
-
     if design.is_empty() {
         return Some(vec![]);
     }
 
-    for (key, towel) in towels {
-        if design.starts_with(towel) {
-            let new_design = &design[towel.len()..];
-            let decomposed = decompose_design(new_design, towels, cache);
-
-            if let Some(mut vect) = decomposed {
-                vect.push(*key);
+    cache.cached(design, |cache| {
+        for (key, towel) in towels {
+            if design.starts_with(towel) {
+                let new_design = &design[towel.len()..];
 
-                cache.insert(design.to_owned(), Some(vect.clone()));
-
-                return Some(vect);
+                if let Some(mut vect) = decompose_design(new_design, towels, cache) {
+                    vect.push(*key);
+                    return Some(vect);
+                }
             }
         }
-    }
-    cache.insert(design.to_owned(), None);
-    return None;
+        None
+    })
 }
 
+/// The count grows about exponentially with the length of the design, so a
+/// long enough one overflows.
 pub fn count_possible_decomps(
     design: &str,
     towels: &TowelSet,
     cache: &mut DesignCache2,
-) -> usize {
-
+) -> Result<usize, SolveError> {
     if design.is_empty() {
-        return 1;
-    }
-
-    if cache.contains_key(design) {
-        return cache[design];
+        return Ok(1);
     }
 
-    let mut decomp_count = 0;
+    cache.cached(design, |cache| {
+        let mut decomp_count: usize = 0;
 
-    for towel in towels {
-        if design.starts_with(towel) {
-            let new_design = &design[towel.len()..];
-            decomp_count += count_possible_decomps(new_design, towels, cache);
+        for towel in towels {
+            if design.starts_with(towel) {
+                let new_design = &design[towel.len()..];
+                let count = count_possible_decomps(new_design, towels, cache)?;
+                decomp_count = decomp_count.checked_add(count).ok_or(SolveError::Overflow)?;
+            }
         }
+        Ok(decomp_count)
+    })
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input = (TowelMap, Vec<String>);
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        // line and column numbers are unaffected by dropping the `\r` of CRLF line ends
+        let input = input.replace("\r\n", "\n");
+        let (input_towels, input_designs) = input
+            .split_once("\n\n")
+            .ok_or_else(|| ParseError::at_end(&input, "a blank line between towels and designs"))?;
+
+        let towels = parse_towels(input_towels)?;
+        let designs = parse_designs(input_designs).map_err(|err| err.within(&input, input_designs))?;
+        Ok((towels, designs))
+    }
+
+    fn part1((towels, designs): &Self::Input) -> Result<usize, SolveError> {
+        let possible = par_map_with(designs, DesignCache::new, |cache, design| {
+            let possible = decompose_design(design, towels, cache).is_some();
+            trace!(design, possible, stats = %cache.stats(), "design cache");
+            possible
+        });
+        Ok(possible.into_iter().filter(|possible| *possible).count())
     }
 
-    cache.insert(design.to_owned(), decomp_count);
-    return decomp_count;
+    fn part2((towels, designs): &Self::Input) -> Result<usize, SolveError> {
+        let towels = TowelSet::from_iter(towels.values().cloned());
+
+        let counts = par_map_with(designs, DesignCache2::new, |cache, design| {
+            let count = count_possible_decomps(design, &towels, cache);
+            trace!(design, ?count, stats = %cache.stats(), "design cache");
+            count
+        });
+        checked_sum(counts.into_iter().collect::<Result<Vec<_>, _>>()?)
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
     #[test]
     fn decompose_design_test() {
         let design = "aaaa";
-        let towels = HashMap::from_iter(vec!["a", "b"].iter().map(|s| s.to_string()).enumerate());
+        let towels = HashMap::from_iter(["a", "b"].iter().map(|s| s.to_string()).enumerate());
         let decomposed = decompose_design(design, &towels, &mut DesignCache::new());
         assert_eq!(decomposed.unwrap(), vec![0, 0, 0, 0]);
 
         let design = "ccc";
-        let towels = HashMap::from_iter(vec!["a", "b"].iter().map(|s| s.to_string()).enumerate());
+        let towels = HashMap::from_iter(["a", "b"].iter().map(|s| s.to_string()).enumerate());
         let decomposed = decompose_design(design, &towels, &mut DesignCache::new());
         assert!(decomposed.is_none());
 
-        let towels = parse_towels("r, wr, b, g, bwu, rb, gb, br");
+        let towels = parse_towels("r, wr, b, g, bwu, rb, gb, br").unwrap();
         assert_eq!(
             decompose_design("brwrr", &towels, &mut DesignCache::new()).is_some(),
             true
@@ -131,12 +186,80 @@ mod tests {
 
     #[test]
     fn count_possible_decomps_test(){
-        let towels = parse_towels("r, wr, b, g, bwu, rb, gb, br");
+        let towels = parse_towels("r, wr, b, g, bwu, rb, gb, br").unwrap();
         let decomp_list: HashSet<String> = HashSet::from_iter(towels.values().cloned());
-        let mut cache = HashMap::<String, usize>::new();
+        let mut cache = DesignCache2::new();
         
         let design = "brwrr";
         let decomp_count = count_possible_decomps(design, &decomp_list, &mut cache);
-        assert_eq!(decomp_count, 2);
+        assert_eq!(decomp_count, Ok(2));
+    }
+
+    #[test]
+    fn overflow_test() {
+        // a row of n `w` can be cut into `w` and `ww` in Fibonacci(n + 1) ways
+        let towels = TowelSet::from_iter(["w".to_string(), "ww".to_string()]);
+        let count = count_possible_decomps(&"w".repeat(92), &towels, &mut DesignCache2::new());
+        assert_eq!(count, Ok(12_200_160_415_121_876_738));
+        let count = count_possible_decomps(&"w".repeat(93), &towels, &mut DesignCache2::new());
+        assert_eq!(count, Err(SolveError::Overflow));
+
+        let towels = TowelMap::from_iter(towels.into_iter().enumerate());
+        let designs = vec!["w".repeat(92), "w".repeat(92)];
+        assert_eq!(Day19::part2(&(towels, designs)), Err(SolveError::Overflow));
+    }
+
+    /// Number of ways to cut `design` into towels, trying every one of the
+    /// 2^(n-1) ways to cut it into pieces.
+    fn reference_decomps(design: &str, towels: &TowelSet) -> usize {
+        if design.is_empty() {
+            return 1;
+        }
+
+        let cut_points = design.len() - 1;
+        (0..1u32 << cut_points)
+            .filter(|cuts| {
+                let mut start = 0;
+                for end in 1..=design.len() {
+                    let is_cut = end == design.len() || cuts & (1 << (end - 1)) != 0;
+                    if is_cut {
+                        if !towels.contains(&design[start..end]) {
+                            return false;
+                        }
+                        start = end;
+                    }
+                }
+                true
+            })
+            .count()
+    }
+
+    proptest! {
+        #[test]
+        fn decomps_match_reference(
+            towels in prop::collection::vec("[wubrg]{1,4}", 1..8),
+            designs in prop::collection::vec("[wubrg]{1,12}", 1..6),
+        ) {
+            let towel_map = TowelMap::from_iter(towels.iter().cloned().enumerate());
+            let towel_set = TowelSet::from_iter(towels.iter().cloned());
+            // one cache for all designs, as in the solution
+            let mut cache = DesignCache::new();
+            let mut count_cache = DesignCache2::new();
+
+            for design in &designs {
+                let expected = reference_decomps(design, &towel_set);
+                let count = count_possible_decomps(design, &towel_set, &mut count_cache);
+                prop_assert_eq!(count, Ok(expected));
+
+                let decomposed = decompose_design(design, &towel_map, &mut cache);
+                prop_assert_eq!(decomposed.is_some(), expected > 0);
+                if let Some(keys) = decomposed {
+                    // towels come out last one first
+                    let joined: String =
+                        keys.iter().rev().map(|key| towel_map[key].as_str()).collect();
+                    prop_assert_eq!(&joined, design);
+                }
+            }
+        }
     }
 }
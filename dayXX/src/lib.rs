@@ -1,14 +1,31 @@
-type DataType = bool;
-type ResultType = i64;
+use aoc_common::{ParseError, Solution, SolveError};
 
-pub fn parse_input(input: &str) -> DataType {
-    todo!()
-}
+pub type DataType = Vec<String>;
+pub type ResultType = u64;
 
-pub fn do_calculations(data: &DataType ) -> ResultType {
-    todo!()
+pub fn parse_input(input: &str) -> Result<DataType, ParseError> {
+    Ok(input.lines().map(str::to_string).collect())
 }
 
+pub struct DayXX;
+
+impl Solution for DayXX {
+    type Input = DataType;
+    type Answer1 = ResultType;
+    type Answer2 = ResultType;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(_: &Self::Input) -> Result<ResultType, SolveError> {
+        Err(SolveError::NotImplemented)
+    }
+
+    fn part2(_: &Self::Input) -> Result<ResultType, SolveError> {
+        Err(SolveError::NotImplemented)
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -16,9 +33,7 @@ mod tests {
 
     #[test]
     fn it_works() {
-        let data;
-        let result = do_calculations(&data);
-        let exp_result;
-        assert_eq!(result, exp_result);
+        let data = parse_input(include_str!("../input/test_input.txt")).unwrap();
+        assert_eq!(DayXX::part1(&data), Err(SolveError::NotImplemented));
     }
-}
\ No newline at end of file
+}